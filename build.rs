@@ -0,0 +1,69 @@
+//! Collects every solution in `src/bin/` into the main binary.
+//!
+//! The generated file declares one module per `src/bin/NN.rs` and `src/bin/YYYY-NN.rs`, and a
+//! `REGISTRY` listing their `SOLUTION` constants, so `all` and `time` can run every day in a
+//! single process.
+//! It also records the version of the compiler, which is stored with benchmark results.
+use std::{env, fs, path::Path, process::Command};
+
+fn main() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+
+    println!("cargo:rerun-if-changed=src/bin");
+
+    // module names, e.g. `day_01` or `day_2016_01`, with the path of the binary.
+    let mut modules: Vec<(String, String)> = fs::read_dir(&bin_dir)
+        .unwrap()
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            if path.extension()? != "rs" {
+                return None;
+            }
+            let stem = path.file_stem()?.to_str()?;
+            let (year, day) = match stem.split_once('-') {
+                Some((year, day)) => (Some(year), day),
+                None => (None, stem),
+            };
+            if year.is_some_and(|y| y.len() != 4 || y.parse::<u16>().is_err()) {
+                return None;
+            }
+            day.parse::<u8>().ok().filter(|d| (1..=25).contains(d))?;
+            (day.len() == 2).then(|| {
+                let name = format!("day_{}", stem.replace('-', "_"));
+                (name, path.to_string_lossy().into_owned())
+            })
+        })
+        .collect();
+
+    modules.sort_unstable();
+
+    let mut out = String::from("// @generated by build.rs, do not edit.\n\n");
+
+    for (name, path) in &modules {
+        out += &format!("#[cfg(not(test))]\n#[path = {path:?}]\nmod {name};\n\n");
+    }
+
+    out += "#[cfg(not(test))]\npub static REGISTRY: advent_of_code::template::Registry =\n";
+    out += "    advent_of_code::template::Registry::new(&[\n";
+    for (name, _) in &modules {
+        out += &format!("        {name}::SOLUTION,\n");
+    }
+    out += "    ]);\n\n";
+
+    // solution tests already run as part of each binary's test target.
+    out += "#[cfg(test)]\npub static REGISTRY: advent_of_code::template::Registry =\n";
+    out += "    advent_of_code::template::Registry::new(&[]);\n";
+
+    let out_path = Path::new(&env::var("OUT_DIR").unwrap()).join("solutions.rs");
+    fs::write(out_path, out).unwrap();
+
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".into());
+    let rustc_version = Command::new(rustc)
+        .arg("--version")
+        .output()
+        .ok()
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .unwrap_or_default();
+    println!("cargo:rustc-env=AOC_RUSTC_VERSION={}", rustc_version.trim());
+}
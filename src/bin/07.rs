@@ -116,14 +116,14 @@ impl<'a> ProgramTree<'a> {
     }
 }
 
-pub fn part_one(input: &str) -> Option<String> {
-    let tree = ProgramTree::from_str(input).unwrap();
-    Some(tree.root().into())
+pub fn part_one(input: &str) -> anyhow::Result<String> {
+    let tree = ProgramTree::from_str(input)?;
+    Ok(tree.root().into())
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let mut tree = ProgramTree::from_str(input).unwrap();
-    Some(tree.balance())
+pub fn part_two(input: &str) -> anyhow::Result<u32> {
+    let mut tree = ProgramTree::from_str(input)?;
+    Ok(tree.balance())
 }
 
 #[cfg(test)]
@@ -133,12 +133,12 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some("tknk".into()));
+        assert_eq!(result.unwrap(), "tknk");
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(60));
+        assert_eq!(result.unwrap(), 60);
     }
 }
@@ -54,14 +54,14 @@ impl Firewall {
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let firewall = Firewall::from_str(input).unwrap();
-    Some(firewall.severity())
+pub fn part_one(input: &str) -> anyhow::Result<u32> {
+    let firewall = Firewall::from_str(input)?;
+    Ok(firewall.severity())
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let firewall = Firewall::from_str(input).unwrap();
-    (0u32..).find(|i| firewall.severity_with_delay(*i) == 0)
+pub fn part_two(input: &str) -> anyhow::Result<Option<u32>> {
+    let firewall = Firewall::from_str(input)?;
+    Ok((0u32..).find(|i| firewall.severity_with_delay(*i) == 0))
 }
 
 #[cfg(test)]
@@ -71,12 +71,12 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(24));
+        assert_eq!(result.unwrap(), 24);
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(10));
+        assert_eq!(result.unwrap(), Some(10));
     }
 }
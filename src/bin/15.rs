@@ -1,4 +1,10 @@
-advent_of_code::solution!(15);
+advent_of_code::solution!(
+    15,
+    params {
+        part_one_pairs: usize = 40_000_000,
+        part_two_pairs: usize = 5_000_000,
+    }
+);
 
 fn lower_16_eq(a: u64, b: u64) -> bool {
     (a & 0xFFFF) == (b & 0xFFFF)
@@ -38,12 +44,12 @@ impl Generator {
 const GEN_A: Generator = Generator { factor: 16807 };
 const GEN_B: Generator = Generator { factor: 48271 };
 
-pub fn part_one(input: &str) -> Option<u64> {
+pub fn part_one(input: &str, params: &Params) -> Option<u64> {
     let lines = input.lines().collect::<Vec<_>>();
     let mut a = lines[0].split_whitespace().nth(4).unwrap().parse().unwrap();
     let mut b = lines[1].split_whitespace().nth(4).unwrap().parse().unwrap();
     let mut count = 0;
-    for _ in 0..40_000_000 {
+    for _ in 0..params.part_one_pairs {
         a = GEN_A.gen(a);
         b = GEN_B.gen(b);
         if lower_16_eq(a, b) {
@@ -53,12 +59,12 @@ pub fn part_one(input: &str) -> Option<u64> {
     Some(count)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+pub fn part_two(input: &str, params: &Params) -> Option<u32> {
     let lines = input.lines().collect::<Vec<_>>();
     let mut a = lines[0].split_whitespace().nth(4).unwrap().parse().unwrap();
     let mut b = lines[1].split_whitespace().nth(4).unwrap().parse().unwrap();
     let mut count = 0;
-    for _ in 0..5_000_000 {
+    for _ in 0..params.part_two_pairs {
         a = GEN_A.gen_mod4(a);
         b = GEN_B.gen_mod8(b);
         if lower_16_eq(a, b) {
@@ -74,13 +80,19 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(
+            &advent_of_code::template::read_file("examples", DAY),
+            &Params::default(),
+        );
         assert_eq!(result, Some(588));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(
+            &advent_of_code::template::read_file("examples", DAY),
+            &Params::default(),
+        );
         assert_eq!(result, Some(309));
     }
 }
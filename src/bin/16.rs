@@ -1,8 +1,13 @@
 use std::{fmt::Display, str::FromStr};
 
-use anyhow::anyhow;
+use anyhow::{anyhow, ensure};
 
-advent_of_code::solution!(16);
+advent_of_code::solution!(
+    16,
+    params {
+        dancers: usize = 16,
+    }
+);
 
 #[repr(u8)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -75,19 +80,12 @@ impl Display for Program {
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-struct LineDance([Program; 16]);
-
-impl Default for LineDance {
-    fn default() -> Self {
-        use Program::*;
-        Self([A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P])
-    }
-}
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct LineDance(Vec<Program>);
 
 impl Display for LineDance {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        for c in self.0 {
+        for c in &self.0 {
             write!(f, "{c}")?;
         }
         Ok(())
@@ -95,6 +93,16 @@ impl Display for LineDance {
 }
 
 impl LineDance {
+    fn new(dancers: usize) -> anyhow::Result<Self> {
+        use Program::*;
+        let programs = [A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P];
+        ensure!(
+            (1..=programs.len()).contains(&dancers),
+            "{dancers} dancers are not supported"
+        );
+        Ok(Self(programs[..dancers].to_vec()))
+    }
+
     fn swap_p(&mut self, a: &Program, b: &Program) {
         let a = self.0.iter().position(|x| x == a).unwrap();
         let b = self.0.iter().position(|x| x == b).unwrap();
@@ -106,7 +114,8 @@ impl LineDance {
     }
 
     fn spin(&mut self, amount: usize) {
-        self.0.rotate_right(amount % 16);
+        let len = self.0.len();
+        self.0.rotate_right(amount % len);
     }
 
     fn process_one(&mut self, i: &Instruction) {
@@ -158,25 +167,25 @@ impl FromStr for Instruction {
     }
 }
 
-pub fn part_one(input: &str) -> Option<String> {
+pub fn part_one(input: &str, params: &Params) -> anyhow::Result<String> {
     let instructions = input
         .trim()
         .split(',')
-        .map(|s| s.parse().unwrap())
-        .collect::<Vec<_>>();
-    let mut line_dance = LineDance::default();
+        .map(str::parse)
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let mut line_dance = LineDance::new(params.dancers)?;
     line_dance.process_many(&instructions);
-    Some(format!("{line_dance}"))
+    Ok(format!("{line_dance}"))
 }
 
-pub fn part_two(input: &str) -> Option<String> {
+pub fn part_two(input: &str, params: &Params) -> anyhow::Result<String> {
     let instructions = input
         .trim()
         .split(',')
-        .map(|s| s.parse().unwrap())
-        .collect::<Vec<_>>();
-    let mut line_dance = LineDance::default();
-    let beginning = LineDance::default();
+        .map(str::parse)
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    let mut line_dance = LineDance::new(params.dancers)?;
+    let beginning = line_dance.clone();
     let mut i = 0;
     let i = loop {
         line_dance.process_many(&instructions);
@@ -188,5 +197,5 @@ pub fn part_two(input: &str) -> Option<String> {
     for _ in 0..(1_000_000_000 % i) {
         line_dance.process_many(&instructions);
     }
-    Some(format!("{line_dance}"))
+    Ok(format!("{line_dance}"))
 }
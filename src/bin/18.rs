@@ -6,11 +6,11 @@ use std::{
 
 use anyhow::{anyhow, Context};
 
-advent_of_code::solution!(18);
+advent_of_code::solution!(18, try_parse = parse);
 
 #[repr(usize)]
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
-enum Register {
+pub enum Register {
     A = 0,
     B = 1,
     F = 2,
@@ -34,7 +34,7 @@ impl FromStr for Register {
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
-enum Value {
+pub enum Value {
     Reg(Register),
     Imm(isize),
 }
@@ -50,7 +50,7 @@ impl FromStr for Value {
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
-enum Instruction {
+pub enum Instruction {
     Set(Register, Value),
     Add(Register, Value),
     Mul(Register, Value),
@@ -262,16 +262,18 @@ impl DoubleComputer {
     }
 }
 
-pub fn part_one(input: &str) -> Option<isize> {
-    let instructions: Vec<_> = input.lines().map(|l| l.parse().unwrap()).collect();
+pub fn parse(input: &str) -> anyhow::Result<Vec<Instruction>> {
+    input.lines().map(str::parse).collect()
+}
+
+pub fn part_one(instructions: &[Instruction]) -> Option<isize> {
     let mut comp = Computer::default();
-    comp.compute_to_first_sound(&instructions)
+    comp.compute_to_first_sound(instructions)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let instructions: Vec<_> = input.lines().map(|l| l.parse().unwrap()).collect();
+pub fn part_two(instructions: &[Instruction]) -> Option<u32> {
     let mut comp = DoubleComputer::default();
-    Some(comp.compute(&instructions))
+    Some(comp.compute(instructions))
 }
 
 #[cfg(test)]
@@ -280,15 +282,17 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let instructions = parse(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&instructions.unwrap());
         assert_eq!(result, Some(4));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file_part(
+        let instructions = parse(&advent_of_code::template::read_file_part(
             "examples", DAY, 1,
         ));
+        let result = part_two(&instructions.unwrap());
         assert_eq!(result, Some(3));
     }
 }
@@ -1,6 +1,12 @@
 use std::str::FromStr;
 
-advent_of_code::solution!(20);
+advent_of_code::solution!(
+    20,
+    params {
+        part_one_ticks: usize = 4000,
+        part_two_ticks: usize = 40,
+    }
+);
 
 #[derive(Clone, Copy, Eq)]
 struct Point {
@@ -97,12 +103,12 @@ impl FromStr for Point {
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
+pub fn part_one(input: &str, params: &Params) -> Option<u32> {
     let mut particles = input
         .lines()
         .map(|l| l.parse::<Point>().unwrap())
         .collect::<Vec<_>>();
-    for _ in 0..4000 {
+    for _ in 0..params.part_one_ticks {
         for p in &mut particles {
             p.update();
         }
@@ -117,12 +123,12 @@ pub fn part_one(input: &str) -> Option<u32> {
     )
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+pub fn part_two(input: &str, params: &Params) -> Option<u32> {
     let mut particles = input
         .lines()
         .map(|l| l.parse::<Point>().unwrap())
         .collect::<Vec<_>>();
-    for _ in 0..40 {
+    for _ in 0..params.part_two_ticks {
         particles.sort();
         let mut dupes = {
             let mut v = Vec::new();
@@ -161,15 +167,19 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(
+            &advent_of_code::template::read_file("examples", DAY),
+            &Params::default(),
+        );
         assert_eq!(result, Some(0));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 1,
-        ));
+        let result = part_two(
+            &advent_of_code::template::read_file_part("examples", DAY, 1),
+            &Params::default(),
+        );
         assert_eq!(result, Some(1));
     }
 }
@@ -2,7 +2,13 @@ use std::{collections::HashMap, fmt::Display, ops::Index, str::FromStr};
 
 use anyhow::anyhow;
 
-advent_of_code::solution!(21);
+advent_of_code::solution!(
+    21,
+    params {
+        part_one_iterations: usize = 5,
+        part_two_iterations: usize = 18,
+    }
+);
 
 #[repr(u8)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -473,41 +479,35 @@ impl Map3 {
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let mut lines = input.lines();
-    let num_times: usize = lines.next().unwrap().parse().unwrap();
-    let mut m2 = Map2::default();
-    let mut m3 = Map3::default();
-    for line in lines {
-        if line.len() < 25 {
-            m2.add_new(line).unwrap();
-        } else {
-            m3.add_new(line).unwrap();
-        }
-    }
-    let mut art = Art::from(ThreeArt::from(START_SQUARE));
-    for _ in 0..num_times {
-        art.transform(&m2, &m3);
-    }
-    Some(art.count() as u32)
+pub fn part_one(input: &str, params: &Params) -> anyhow::Result<u32> {
+    enhance(input, params.part_one_iterations)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let lines = input.lines().skip(1);
+pub fn part_two(input: &str, params: &Params) -> anyhow::Result<u32> {
+    enhance(input, params.part_two_iterations)
+}
+
+fn enhance(input: &str, iterations: usize) -> anyhow::Result<u32> {
     let mut m2 = Map2::default();
     let mut m3 = Map3::default();
-    for line in lines {
+    for line in input.lines() {
+        // inputs used to start with the number of iterations, which is a parameter now.
+        if let Ok(count) = line.parse::<usize>() {
+            return Err(anyhow!(
+                "the input starts with an iteration count, remove it from the input and pass `--param part_one_iterations={count}` instead"
+            ));
+        }
         if line.len() < 25 {
-            m2.add_new(line).unwrap();
+            m2.add_new(line)?;
         } else {
-            m3.add_new(line).unwrap();
+            m3.add_new(line)?;
         }
     }
     let mut art = Art::from(ThreeArt::from(START_SQUARE));
-    for _ in 0..18 {
+    for _ in 0..iterations {
         art.transform(&m2, &m3);
     }
-    Some(art.count() as u32)
+    Ok(art.count() as u32)
 }
 
 #[cfg(test)]
@@ -515,8 +515,12 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(12));
+    fn test_part_one_with_iteration_count() {
+        let input = format!(
+            "2\n{}",
+            advent_of_code::template::read_file("examples", DAY)
+        );
+        let err = part_one(&input, &Params::default()).unwrap_err();
+        assert!(err.to_string().contains("--param part_one_iterations=2"));
     }
 }
@@ -2,7 +2,7 @@ use std::{collections::HashMap, str::FromStr};
 
 use anyhow::anyhow;
 
-advent_of_code::solution!(25);
+advent_of_code::solution!(25, 1);
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug, Hash, Default)]
 enum State {
@@ -203,14 +203,10 @@ impl Computer {
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let rules = input.parse().unwrap();
+pub fn part_one(input: &str) -> anyhow::Result<u32> {
+    let rules = input.parse()?;
     let mut computer = Computer::default();
-    Some(computer.compute(&rules))
-}
-
-pub fn part_two(_input: &str) -> Option<u32> {
-    Some(1)
+    Ok(computer.compute(&rules))
 }
 
 #[cfg(test)]
@@ -220,6 +216,6 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(3));
+        assert_eq!(result.unwrap(), 3);
     }
 }
@@ -1,77 +1,264 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, download, examples, read, run_day, scaffold, solve, test, time, verify,
+};
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
+use advent_of_code::template::{commands::scaffold::ScaffoldOptions, Day, Puzzle};
 #[cfg(feature = "today")]
 use std::process;
 
+mod solutions {
+    include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+}
+
 mod args {
-    use advent_of_code::template::Day;
+    use advent_of_code::template::{
+        commands::{
+            run_day,
+            scaffold::ScaffoldOptions,
+            solve::BuildOptions,
+            time::{self, CompareArgs},
+        },
+        params::Param,
+        parse_year,
+        runner::{BenchOptions, InputSource},
+        Day, Format, Puzzle, RunLimits, Years,
+    };
     use std::process;
 
     pub enum AppArguments {
         Download {
-            day: Day,
+            puzzle: Puzzle,
         },
         Read {
-            day: Day,
+            puzzle: Puzzle,
+            part: Option<u8>,
+        },
+        Examples {
+            puzzle: Puzzle,
+            accept_all: bool,
         },
         Scaffold {
-            day: Day,
+            puzzle: Puzzle,
             download: bool,
+            options: ScaffoldOptions,
         },
         Solve {
-            day: Day,
-            release: bool,
-            dhat: bool,
+            puzzle: Puzzle,
+            build: BuildOptions,
             submit: Option<u8>,
+            accept: bool,
+            input: InputSource,
+            params: Vec<Param>,
+            bench: Option<BenchOptions>,
+            watch: bool,
         },
-        All {
+        Test {
+            puzzle: Puzzle,
             release: bool,
+            watch: bool,
+        },
+        All {
+            years: Years,
+            jobs: Option<usize>,
+            limits: RunLimits,
+            format: Format,
+            dashboard: bool,
         },
         Time {
+            years: Years,
             all: bool,
             day: Option<Day>,
             store: bool,
+            bench: BenchOptions,
+            jobs: Option<usize>,
+            limits: RunLimits,
+            compare: Option<CompareArgs>,
+            format: Format,
+            dashboard: bool,
+        },
+        Verify {
+            years: Years,
+            day: Option<Day>,
+            jobs: Option<usize>,
+            limits: RunLimits,
+            format: Format,
+            dashboard: bool,
+        },
+        RunDay {
+            puzzle: Puzzle,
+            bench: Option<BenchOptions>,
         },
         #[cfg(feature = "today")]
         Today,
     }
 
+    /// The puzzle of the `<day>` argument, in the year passed with `--year` or the primary year.
+    fn puzzle(args: &mut pico_args::Arguments) -> Result<Puzzle, pico_args::Error> {
+        let year = args.opt_value_from_fn("--year", parse_year)?;
+        Ok(Puzzle::in_year(year, args.free_from_str()?))
+    }
+
+    /// The years passed with `--year`, either a year or `all`, defaults to the primary year.
+    fn years(args: &mut pico_args::Arguments) -> Result<Years, pico_args::Error> {
+        Ok(args
+            .opt_value_from_str("--year")?
+            .unwrap_or_else(Years::primary))
+    }
+
+    /// The report format passed with `--format`, defaults to text.
+    fn format(args: &mut pico_args::Arguments) -> Result<Format, pico_args::Error> {
+        Ok(args.opt_value_from_str("--format")?.unwrap_or_default())
+    }
+
+    /// Whether `--dashboard` was passed, which needs the text `format`.
+    fn dashboard(
+        args: &mut pico_args::Arguments,
+        format: Format,
+    ) -> Result<bool, Box<dyn std::error::Error>> {
+        let dashboard = args.contains("--dashboard");
+        if dashboard && format != Format::Text {
+            return Err("`--dashboard` can't be combined with `--format`".into());
+        }
+        Ok(dashboard)
+    }
+
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
         let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
-                release: args.contains("--release"),
-            },
+            Some("all") => {
+                // deprecated: the alias always builds in release and days run in-process.
+                args.contains("--release");
+                let years = years(&mut args)?;
+                let jobs = args.opt_value_from_str(["-j", "--jobs"])?;
+                let limits = RunLimits::parse(&mut args)?;
+                let format = format(&mut args)?;
+
+                AppArguments::All {
+                    years,
+                    jobs,
+                    limits,
+                    format,
+                    dashboard: dashboard(&mut args, format)?,
+                }
+            }
             Some("time") => {
+                let years = years(&mut args)?;
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let bench = BenchOptions::parse(&mut args)?;
+                let jobs = args.opt_value_from_str(["-j", "--jobs"])?;
+                let limits = RunLimits::parse(&mut args)?;
+                let reference: Option<String> = args.opt_value_from_str("--compare")?;
+                let threshold = args.opt_value_from_str("--threshold")?;
+                let format = format(&mut args)?;
+                let dashboard = dashboard(&mut args, format)?;
 
                 AppArguments::Time {
+                    years,
                     all,
                     day: args.opt_free_from_str()?,
                     store,
+                    bench,
+                    jobs,
+                    limits,
+                    compare: reference.map(|reference| CompareArgs {
+                        reference,
+                        threshold: threshold.unwrap_or(time::DEFAULT_THRESHOLD),
+                    }),
+                    format,
+                    dashboard,
                 }
             }
+            Some(run_day::COMMAND) => {
+                let puzzle = puzzle(&mut args)?;
+                let bench = if args.contains("--time") {
+                    Some(BenchOptions::parse(&mut args)?)
+                } else {
+                    None
+                };
+
+                AppArguments::RunDay { puzzle, bench }
+            }
             Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+                puzzle: puzzle(&mut args)?,
             },
             Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+                puzzle: puzzle(&mut args)?,
+                part: args.opt_value_from_str("--part")?,
+            },
+            Some("examples") => AppArguments::Examples {
+                puzzle: puzzle(&mut args)?,
+                accept_all: args.contains("--accept-all"),
             },
             Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
+                puzzle: puzzle(&mut args)?,
                 download: args.contains("--download"),
+                options: ScaffoldOptions {
+                    template: args.opt_value_from_str("--template")?,
+                    answer_type: args.opt_value_from_str("--answer-type")?,
+                    force: args.contains("--force"),
+                },
             },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
+            Some("solve") => {
+                let puzzle = puzzle(&mut args)?;
+                let build = BuildOptions {
+                    release: args.contains("--release"),
+                    dhat: args.contains("--dhat"),
+                    alloc_stats: args.contains("--alloc-stats"),
+                };
+                let submit = args.opt_value_from_str("--submit")?;
+                let accept = args.contains("--accept");
+                let input = InputSource::parse(&mut args)?;
+                let params = args.values_from_str("--param")?;
+                let bench = if args.contains("--time") {
+                    Some(BenchOptions::parse(&mut args)?)
+                } else {
+                    None
+                };
+                let watch = args.contains("--watch");
+
+                if watch && (submit.is_some() || accept || input == InputSource::Stdin) {
+                    return Err(
+                        "`--watch` can't be combined with `--submit`, `--accept` or `--stdin`"
+                            .into(),
+                    );
+                }
+
+                AppArguments::Solve {
+                    puzzle,
+                    build,
+                    submit,
+                    accept,
+                    input,
+                    params,
+                    bench,
+                    watch,
+                }
+            }
+            Some("test") => AppArguments::Test {
+                puzzle: puzzle(&mut args)?,
                 release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
+                watch: args.contains("--watch"),
             },
+            Some("verify") => {
+                let years = years(&mut args)?;
+                let jobs = args.opt_value_from_str(["-j", "--jobs"])?;
+                let limits = RunLimits::parse(&mut args)?;
+                let format = format(&mut args)?;
+                let dashboard = dashboard(&mut args, format)?;
+
+                AppArguments::Verify {
+                    years,
+                    day: args.opt_free_from_str()?,
+                    jobs,
+                    limits,
+                    format,
+                    dashboard,
+                }
+            }
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
             Some(x) => {
@@ -100,29 +287,109 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day, download } => {
-                scaffold::handle(day);
+            AppArguments::All {
+                years,
+                jobs,
+                limits,
+                format,
+                dashboard,
+            } => all::handle(&solutions::REGISTRY, years, jobs, limits, format, dashboard),
+            AppArguments::Time {
+                years,
+                day,
+                all,
+                store,
+                bench,
+                jobs,
+                limits,
+                compare,
+                format,
+                dashboard,
+            } => time::handle(
+                &solutions::REGISTRY,
+                years,
+                day,
+                all,
+                store,
+                bench,
+                jobs,
+                limits,
+                compare,
+                format,
+                dashboard,
+            ),
+            AppArguments::RunDay { puzzle, bench } => {
+                run_day::handle(&solutions::REGISTRY, puzzle, bench);
+            }
+            AppArguments::Download { puzzle } => download::handle(puzzle),
+            AppArguments::Read { puzzle, part } => read::handle(puzzle, part),
+            AppArguments::Examples { puzzle, accept_all } => examples::handle(puzzle, accept_all),
+            AppArguments::Scaffold {
+                puzzle,
+                download,
+                options,
+            } => {
+                // Download first, so that the template can use the puzzle title.
                 if download {
-                    download::handle(day);
+                    download::handle(puzzle);
                 }
+                scaffold::handle(puzzle, &options);
             }
             AppArguments::Solve {
-                day,
-                release,
-                dhat,
+                puzzle,
+                build,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                accept,
+                input,
+                params,
+                bench,
+                watch,
+            } => {
+                if watch {
+                    solve::watch(puzzle, build, bench.as_ref(), &input, &params);
+                } else {
+                    solve::handle(
+                        puzzle,
+                        build,
+                        bench.as_ref(),
+                        submit,
+                        accept,
+                        &input,
+                        &params,
+                    );
+                }
+            }
+            AppArguments::Test {
+                puzzle,
+                release,
+                watch,
+            } => test::handle(puzzle, release, watch),
+            AppArguments::Verify {
+                years,
+                day,
+                jobs,
+                limits,
+                format,
+                dashboard,
+            } => {
+                verify::handle(
+                    &solutions::REGISTRY,
+                    years,
+                    day,
+                    jobs,
+                    limits,
+                    format,
+                    dashboard,
+                );
+            }
             #[cfg(feature = "today")]
             AppArguments::Today => {
                 match Day::today() {
                     Some(day) => {
-                        scaffold::handle(day);
-                        download::handle(day);
-                        read::handle(day)
+                        let puzzle = Puzzle::primary(day);
+                        download::handle(puzzle);
+                        scaffold::handle(puzzle, &ScaffoldOptions::default());
+                        read::handle(puzzle, None)
                     }
                     None => {
                         eprintln!(
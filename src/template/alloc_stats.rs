@@ -0,0 +1,201 @@
+/// Heap allocations of solution parts, counted by an opt-in global allocator.
+///
+/// With the `alloc-stats` feature, every allocation of the process goes through
+/// [`CountingAllocator`], and [`measure`] reports the allocations of a single run of a part.
+/// Counting adds a few atomic operations to every allocation, so it is off by default.
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    collections::HashMap,
+    sync::atomic::{AtomicU64, Ordering},
+};
+use tinyjson::JsonValue;
+
+/// Whether the counting allocator is installed. `dhat-heap` takes precedence if both are enabled.
+pub const ENABLED: bool = cfg!(all(feature = "alloc-stats", not(feature = "dhat-heap")));
+
+static CURRENT: AtomicU64 = AtomicU64::new(0);
+static PEAK: AtomicU64 = AtomicU64::new(0);
+static TOTAL: AtomicU64 = AtomicU64::new(0);
+static COUNT: AtomicU64 = AtomicU64::new(0);
+
+/// Allocations of a single run of a part.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    /// Most bytes that were allocated at the same time, above what was allocated before the run.
+    pub peak_bytes: u64,
+    /// Sum of the sizes of all allocations, including reallocations.
+    pub total_bytes: u64,
+    /// Number of allocations, including reallocations.
+    pub count: u64,
+}
+
+/// A global allocator that delegates to [`System`] and counts allocations.
+pub struct CountingAllocator;
+
+fn record_alloc(size: u64) {
+    COUNT.fetch_add(1, Ordering::Relaxed);
+    TOTAL.fetch_add(size, Ordering::Relaxed);
+    let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK.fetch_max(current, Ordering::Relaxed);
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            record_alloc(layout.size() as u64);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            record_alloc(layout.size() as u64);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        CURRENT.fetch_sub(layout.size() as u64, Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            // the old block is released once the new one is allocated.
+            record_alloc(new_size as u64);
+            CURRENT.fetch_sub(layout.size() as u64, Ordering::Relaxed);
+        }
+        new_ptr
+    }
+}
+
+/// Run `f` and return the allocations it made, or `None` if the allocator is not installed.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+    if !ENABLED {
+        return (f(), None);
+    }
+
+    let current = CURRENT.load(Ordering::Relaxed);
+    PEAK.store(current, Ordering::Relaxed);
+    let total = TOTAL.load(Ordering::Relaxed);
+    let count = COUNT.load(Ordering::Relaxed);
+
+    let result = f();
+
+    let stats = AllocStats {
+        peak_bytes: PEAK.load(Ordering::Relaxed).saturating_sub(current),
+        total_bytes: TOTAL.load(Ordering::Relaxed) - total,
+        count: COUNT.load(Ordering::Relaxed) - count,
+    };
+
+    (result, Some(stats))
+}
+
+/// Format a number of bytes with a binary unit, e.g. `1.5 KiB`.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&AllocStats> for JsonValue {
+    fn from(value: &AllocStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        {
+            map.insert(
+                "peak_bytes".into(),
+                JsonValue::Number(value.peak_bytes as f64),
+            );
+            map.insert(
+                "total_bytes".into(),
+                JsonValue::Number(value.total_bytes as f64),
+            );
+            map.insert("count".into(), JsonValue::Number(value.count as f64));
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for AllocStats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected allocations to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().map(|v| *v as u64))
+                .ok_or(format!("Expected alloc.{key} to be a number."))
+        };
+
+        Ok(AllocStats {
+            peak_bytes: number("peak_bytes")?,
+            total_bytes: number("total_bytes")?,
+            count: number("count")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::{format_bytes, measure, AllocStats, ENABLED};
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+
+    #[test]
+    fn round_trips_json() {
+        let stats = AllocStats {
+            peak_bytes: 4096,
+            total_bytes: 10_000,
+            count: 12,
+        };
+        assert_eq!(AllocStats::try_from(&JsonValue::from(&stats)), Ok(stats));
+    }
+
+    #[test]
+    fn measures_allocations() {
+        let (len, stats) = measure(|| vec![0_u8; 4096].len());
+        assert_eq!(len, 4096);
+
+        if ENABLED {
+            let stats = stats.unwrap();
+            assert!(stats.count >= 1);
+            assert!(stats.total_bytes >= 4096);
+        } else {
+            assert_eq!(stats, None);
+        }
+    }
+}
@@ -0,0 +1,401 @@
+/// Known answers, stored in `data/answers.json`.
+///
+/// Answers are accepted with `cargo solve <day> --accept` or when a submission was correct,
+/// and checked against the current output of all solutions by `cargo verify`.
+use std::{collections::HashMap, fs, io, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::config;
+use crate::template::report::{PartReport, Status, PARSE_PART};
+use crate::template::{Day, Puzzle};
+
+/// Name of the answers file in the data directory.
+static ANSWERS_FILE_NAME: &str = "answers.json";
+
+/// Known answers of a single day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Answer {
+    pub day: Day,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+/// Known answers for a set of days.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub data: Vec<Answer>,
+}
+
+impl Answers {
+    /// Dehydrate the answers of `year` to a JSON file.
+    pub fn store_file(&self, year: u16) -> Result<(), io::Error> {
+        let json = JsonValue::from(self);
+        let mut file = config::get()
+            .paths_for(year)
+            .create_data_file(ANSWERS_FILE_NAME)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate the answers of `year` from a JSON file. If not present, returns no answers.
+    pub fn read_from_file(year: u16) -> Result<Self, String> {
+        match fs::read_to_string(config::get().paths_for(year).data_file(ANSWERS_FILE_NAME)) {
+            Ok(s) => Answers::try_from(s),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    /// Returns the known answer of a part, if there is one.
+    pub fn get(&self, day: Day, part: u8) -> Option<&str> {
+        let answer = self.data.iter().find(|a| a.day == day)?;
+        match part {
+            1 => answer.part_1.as_deref(),
+            2 => answer.part_2.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Set the known answer of a part, replacing any previous answer.
+    pub fn set(&mut self, day: Day, part: u8, value: &str) {
+        let index = match self.data.iter().position(|a| a.day == day) {
+            Some(index) => index,
+            None => {
+                self.data.push(Answer {
+                    day,
+                    part_1: None,
+                    part_2: None,
+                });
+                self.data.sort_unstable_by_key(|a| a.day);
+                self.data.iter().position(|a| a.day == day).unwrap()
+            }
+        };
+
+        let answer = &mut self.data[index];
+        match part {
+            1 => answer.part_1 = Some(value.into()),
+            2 => answer.part_2 = Some(value.into()),
+            _ => {}
+        }
+    }
+}
+
+/// Record `value` as the known answer of a part in the `answers.json` of its year.
+pub fn accept(puzzle: Puzzle, part: u8, value: &str) -> Result<(), String> {
+    let mut answers = Answers::read_from_file(puzzle.year)?;
+    answers.set(puzzle.day, part, value);
+    answers.store_file(puzzle.year).map_err(|e| e.to_string())
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Result of comparing a part's output with its known answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    /// The part produced the known answer.
+    Correct,
+    /// The part produced a different answer, or none at all.
+    Mismatch {
+        expected: String,
+        actual: Option<String>,
+    },
+    /// The part produced an answer, but there is no known answer to compare it with.
+    Missing { actual: String },
+    /// The part or the parse step returned an error.
+    Failed { error: Option<String> },
+}
+
+impl Verdict {
+    /// Mismatches and failures fail verification, missing answers don't.
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Verdict::Mismatch { .. } | Verdict::Failed { .. })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Check {
+    pub day: Day,
+    /// The part number, or [`PARSE_PART`] for a failed parse step.
+    pub part: u8,
+    pub verdict: Verdict,
+}
+
+/// Compare the reports of `days` with their known answers, checking only the given parts.
+/// Parts that have neither an answer nor a known answer are skipped.
+pub fn verify(answers: &Answers, reports: &[PartReport], days: &[(Day, &[u8])]) -> Vec<Check> {
+    let mut checks = vec![];
+
+    for &(day, parts) in days {
+        let report = |part: u8| reports.iter().find(|r| r.day == day && r.part == part);
+
+        if let Some(report) = report(PARSE_PART).filter(|r| r.status == Status::Failed) {
+            checks.push(Check {
+                day,
+                part: PARSE_PART,
+                verdict: Verdict::Failed {
+                    error: report.error.clone(),
+                },
+            });
+        }
+
+        for &part in parts {
+            let expected = answers.get(day, part);
+
+            let verdict = match (report(part), expected) {
+                (Some(r), _) if r.status == Status::Failed => Verdict::Failed {
+                    error: r.error.clone(),
+                },
+                (
+                    Some(PartReport {
+                        answer: Some(actual),
+                        ..
+                    }),
+                    Some(expected),
+                ) => {
+                    if actual == expected {
+                        Verdict::Correct
+                    } else {
+                        Verdict::Mismatch {
+                            expected: expected.into(),
+                            actual: Some(actual.clone()),
+                        }
+                    }
+                }
+                (
+                    Some(PartReport {
+                        answer: Some(actual),
+                        ..
+                    }),
+                    None,
+                ) => Verdict::Missing {
+                    actual: actual.clone(),
+                },
+                (_, Some(expected)) => Verdict::Mismatch {
+                    expected: expected.into(),
+                    actual: None,
+                },
+                (_, None) => continue,
+            };
+
+            checks.push(Check { day, part, verdict });
+        }
+    }
+
+    checks
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Answers> for JsonValue {
+    fn from(value: &Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Answers {
+            data: json_data
+                .iter()
+                .map(Answer::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Answer> for JsonValue {
+    fn from(value: &Answer) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+
+        for (key, part) in [("part_1", &value.part_1), ("part_2", &value.part_2)] {
+            map.insert(
+                key.into(),
+                match part {
+                    Some(x) => JsonValue::String(x.clone()),
+                    None => JsonValue::Null,
+                },
+            );
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Answer {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected answer to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected answer.day to be a Day struct.")?;
+
+        let part = |key: &str| {
+            json.get(key)
+                .map(|v| if v.is_null() { None } else { v.get::<String>() })
+                .ok_or(format!("Expected answer.{key} to be null or string."))
+                .map(|v| v.cloned())
+        };
+
+        Ok(Answer {
+            day,
+            part_1: part("part_1")?,
+            part_2: part("part_2")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{verify, Answers, Check, Verdict};
+    use crate::{
+        day,
+        template::{
+            registry::ALL_PARTS,
+            report::{PartReport, Status},
+        },
+    };
+
+    fn report(day: u8, part: u8, answer: Option<&str>, status: Status) -> PartReport {
+        PartReport {
+            error: (status == Status::Failed).then(|| "boom".into()),
+            ..PartReport::test(
+                crate::template::Day::new(day).unwrap(),
+                part,
+                answer,
+                status,
+            )
+        }
+    }
+
+    fn get_mock_answers() -> Answers {
+        let mut answers = Answers::default();
+        answers.set(day!(2), 1, "18");
+        answers.set(day!(1), 1, "3");
+        answers.set(day!(1), 2, "4");
+        answers
+    }
+
+    #[test]
+    fn sets_and_gets_answers() {
+        let mut answers = get_mock_answers();
+        assert_eq!(answers.data[0].day, day!(1));
+        assert_eq!(answers.get(day!(1), 2), Some("4"));
+        assert_eq!(answers.get(day!(2), 2), None);
+        assert_eq!(answers.get(day!(3), 1), None);
+
+        answers.set(day!(1), 2, "5");
+        assert_eq!(answers.get(day!(1), 2), Some("5"));
+    }
+
+    #[test]
+    fn round_trips_json() {
+        let answers = get_mock_answers();
+        let json = tinyjson::JsonValue::from(&answers).stringify().unwrap();
+        assert_eq!(Answers::try_from(json).unwrap(), answers);
+    }
+
+    #[test]
+    fn verifies_reports() {
+        let answers = get_mock_answers();
+        let reports = vec![
+            report(1, 1, Some("3"), Status::Solved),
+            report(1, 2, Some("5"), Status::Solved),
+            report(2, 1, None, Status::Failed),
+            report(2, 2, Some("7"), Status::Solved),
+            report(3, 1, None, Status::Unsolved),
+        ];
+
+        let days = [day!(1), day!(2), day!(3), day!(4)].map(|d| (d, ALL_PARTS));
+        let checks = verify(&answers, &reports, &days);
+
+        assert_eq!(
+            checks,
+            vec![
+                Check {
+                    day: day!(1),
+                    part: 1,
+                    verdict: Verdict::Correct,
+                },
+                Check {
+                    day: day!(1),
+                    part: 2,
+                    verdict: Verdict::Mismatch {
+                        expected: "4".into(),
+                        actual: Some("5".into()),
+                    },
+                },
+                Check {
+                    day: day!(2),
+                    part: 1,
+                    verdict: Verdict::Failed {
+                        error: Some("boom".into()),
+                    },
+                },
+                Check {
+                    day: day!(2),
+                    part: 2,
+                    verdict: Verdict::Missing { actual: "7".into() },
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_known_answers_without_output() {
+        let mut answers = Answers::default();
+        answers.set(day!(5), 1, "42");
+
+        let checks = verify(&answers, &[], &[(day!(5), ALL_PARTS)]);
+        assert_eq!(
+            checks[0].verdict,
+            Verdict::Mismatch {
+                expected: "42".into(),
+                actual: None,
+            }
+        );
+        assert!(checks[0].verdict.is_failure());
+    }
+
+    #[test]
+    fn skips_undeclared_parts() {
+        let mut answers = Answers::default();
+        answers.set(day!(25), 1, "3");
+        answers.set(day!(25), 2, "1");
+
+        let reports = vec![report(25, 1, Some("3"), Status::Solved)];
+        let checks = verify(&answers, &reports, &[(day!(25), &[1])]);
+        assert_eq!(checks.len(), 1);
+        assert_eq!(checks[0].verdict, Verdict::Correct);
+    }
+}
@@ -0,0 +1,460 @@
+/// A small HTTP client for the Advent of Code website.
+///
+/// Downloads inputs and puzzle descriptions, and submits answers. Authentication uses the
+/// `session` cookie of a logged-in browser, read from the environment variable or file that is
+/// configured in `aoc.toml`, `AOC_SESSION` and `~/.adventofcode.session` by default.
+use std::{env, fmt::Display, fs, io, time::Duration};
+
+use crate::template::{config, html, Puzzle};
+
+/// Environment variable that overrides the base URL of the website, e.g. for testing.
+pub const BASE_URL_ENV: &str = "AOC_BASE_URL";
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+const USER_AGENT: &str = "github.com/fspoettel/advent-of-code-rust";
+
+/// Hint the website gives for a wrong answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Hint {
+    TooHigh,
+    TooLow,
+}
+
+#[derive(Debug)]
+pub enum AocClientError {
+    /// No session cookie was found, but the request requires one.
+    MissingSession,
+    /// The session cookie was rejected.
+    Unauthorized,
+    /// The puzzle has not unlocked yet.
+    TooEarly,
+    /// An answer was submitted too recently.
+    RateLimited { wait: Option<Duration> },
+    /// The submitted answer is wrong.
+    WrongAnswer {
+        hint: Option<Hint>,
+        wait: Option<Duration>,
+    },
+    /// The part was already solved, or its previous part is not solved yet.
+    WrongLevel,
+    /// The website answered with an unexpected status code.
+    BadStatus(u16),
+    /// The website answered with a page that could not be understood.
+    UnexpectedResponse(String),
+    /// The request could not be sent or its response could not be read.
+    Transport(String),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::MissingSession => {
+                let session = &config::get().session;
+                write!(
+                    f,
+                    "no session cookie found. Set {} or create {}.",
+                    session.env, session.file
+                )
+            }
+            AocClientError::Unauthorized => {
+                write!(f, "the session cookie was rejected, it might have expired.")
+            }
+            AocClientError::TooEarly => write!(f, "the puzzle has not unlocked yet."),
+            AocClientError::RateLimited { wait } => {
+                write!(f, "you gave an answer too recently.")?;
+                write_wait(f, *wait)
+            }
+            AocClientError::WrongAnswer { hint, wait } => {
+                write!(f, "that's not the right answer")?;
+                match hint {
+                    Some(Hint::TooHigh) => write!(f, ", your answer is too high.")?,
+                    Some(Hint::TooLow) => write!(f, ", your answer is too low.")?,
+                    None => write!(f, ".")?,
+                }
+                write_wait(f, *wait)
+            }
+            AocClientError::WrongLevel => write!(
+                f,
+                "you don't seem to be solving the right level. Did you already complete it?"
+            ),
+            AocClientError::BadStatus(status) => {
+                write!(f, "unexpected response status {status}.")
+            }
+            AocClientError::UnexpectedResponse(message) => {
+                write!(f, "unexpected response: {message}")
+            }
+            AocClientError::Transport(e) => write!(f, "request failed: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for AocClientError {}
+
+fn write_wait(f: &mut std::fmt::Formatter<'_>, wait: Option<Duration>) -> std::fmt::Result {
+    match wait {
+        Some(wait) => write!(f, " Please wait {}s before trying again.", wait.as_secs()),
+        None => Ok(()),
+    }
+}
+
+pub struct AocClient {
+    agent: ureq::Agent,
+    base_url: String,
+    session: Option<String>,
+}
+
+impl AocClient {
+    pub fn new(base_url: &str, session: Option<String>) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(USER_AGENT)
+            .redirects(0)
+            .timeout(Duration::from_secs(30))
+            .build();
+
+        Self {
+            agent,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session,
+        }
+    }
+
+    /// Create a client with the configured session, using the base URL from [`BASE_URL_ENV`] if
+    /// it is set.
+    pub fn from_env() -> Self {
+        let base_url = env::var(BASE_URL_ENV).unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        Self::new(&base_url, read_session())
+    }
+
+    /// Download the input of `puzzle`.
+    pub fn input(&self, puzzle: Puzzle) -> Result<String, AocClientError> {
+        self.require_session()?;
+        self.get(&format!("{}/input", Self::puzzle_path(puzzle)))
+    }
+
+    /// Download the description of `puzzle` and convert it to markdown.
+    /// The description of part two is only included once part one has been solved.
+    pub fn puzzle(&self, puzzle: Puzzle) -> Result<String, AocClientError> {
+        let html = self.get(&Self::puzzle_path(puzzle))?;
+        let markdown = html::articles_to_markdown(&html, &self.base_url);
+
+        if markdown.is_empty() {
+            return Err(AocClientError::UnexpectedResponse(
+                "the puzzle page contains no description.".into(),
+            ));
+        }
+
+        Ok(markdown)
+    }
+
+    /// Submit `answer` for one part of `puzzle`. Returns the website's message on success.
+    pub fn submit(&self, puzzle: Puzzle, part: u8, answer: &str) -> Result<String, AocClientError> {
+        let session = self.require_session()?;
+        let url = format!("{}{}/answer", self.base_url, Self::puzzle_path(puzzle));
+
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &format!("session={session}"))
+            .send_form(&[("level", &part.to_string()), ("answer", answer)]);
+
+        let html = read_response(response)?;
+        parse_submission(&html::articles_to_markdown(&html, &self.base_url))
+    }
+
+    fn puzzle_path(puzzle: Puzzle) -> String {
+        format!("/{}/day/{}", puzzle.year, puzzle.day.into_inner())
+    }
+
+    fn require_session(&self) -> Result<&str, AocClientError> {
+        self.session
+            .as_deref()
+            .ok_or(AocClientError::MissingSession)
+    }
+
+    fn get(&self, path: &str) -> Result<String, AocClientError> {
+        let mut request = self.agent.get(&format!("{}{path}", self.base_url));
+
+        if let Some(session) = &self.session {
+            request = request.set("Cookie", &format!("session={session}"));
+        }
+
+        read_response(request.call())
+    }
+}
+
+fn read_response(response: Result<ureq::Response, ureq::Error>) -> Result<String, AocClientError> {
+    match response {
+        // requests without a valid session are redirected to the login page.
+        Ok(response) if (300..400).contains(&response.status()) => {
+            Err(AocClientError::Unauthorized)
+        }
+        Ok(response) => response
+            .into_string()
+            .map_err(|e| AocClientError::Transport(e.to_string())),
+        Err(ureq::Error::Status(404, _)) => Err(AocClientError::TooEarly),
+        Err(ureq::Error::Status(400 | 401 | 403, _)) => Err(AocClientError::Unauthorized),
+        Err(ureq::Error::Status(status, _)) => Err(AocClientError::BadStatus(status)),
+        Err(ureq::Error::Transport(e)) => Err(AocClientError::Transport(e.to_string())),
+    }
+}
+
+/// Interpret the message the website shows after submitting an answer.
+fn parse_submission(message: &str) -> Result<String, AocClientError> {
+    let message = message.trim();
+    let wait = parse_wait(message);
+
+    if message.contains("That's the right answer") {
+        Ok(message.to_string())
+    } else if message.contains("That's not the right answer") {
+        let hint = if message.contains("too high") {
+            Some(Hint::TooHigh)
+        } else if message.contains("too low") {
+            Some(Hint::TooLow)
+        } else {
+            None
+        };
+        Err(AocClientError::WrongAnswer { hint, wait })
+    } else if message.contains("You gave an answer too recently") {
+        Err(AocClientError::RateLimited { wait })
+    } else if message.contains("You don't seem to be solving the right level") {
+        Err(AocClientError::WrongLevel)
+    } else if message.contains("identify yourself") {
+        Err(AocClientError::Unauthorized)
+    } else {
+        Err(AocClientError::UnexpectedResponse(message.to_string()))
+    }
+}
+
+/// Parse the waiting time out of messages like "You have 1m 5s left to wait." or
+/// "Please wait one minute before trying again.".
+fn parse_wait(message: &str) -> Option<Duration> {
+    if let Some(start) = message.find("You have ") {
+        let rest = &message[start + "You have ".len()..];
+        let end = rest.find(" left to wait")?;
+        let secs = rest[..end]
+            .split_whitespace()
+            .map(|part| {
+                let (value, unit) = part.split_at(part.find(|c: char| !c.is_ascii_digit())?);
+                let value: u64 = value.parse().ok()?;
+                match unit {
+                    "h" => Some(value * 3600),
+                    "m" => Some(value * 60),
+                    "s" => Some(value),
+                    _ => None,
+                }
+            })
+            .sum::<Option<u64>>()?;
+        return Some(Duration::from_secs(secs));
+    }
+
+    let start = message.find("wait ")?;
+    let mut words = message[start + "wait ".len()..].split_whitespace();
+    let amount = match words.next()? {
+        "one" | "a" => 1,
+        "two" => 2,
+        "three" => 3,
+        "five" => 5,
+        "ten" => 10,
+        n => n.parse().ok()?,
+    };
+    match words.next()?.trim_end_matches(['.', ',']) {
+        "second" | "seconds" => Some(Duration::from_secs(amount)),
+        "minute" | "minutes" => Some(Duration::from_secs(amount * 60)),
+        _ => None,
+    }
+}
+
+fn read_session() -> Option<String> {
+    let source = &config::get().session;
+
+    if let Ok(session) = env::var(&source.env) {
+        return Some(session.trim().to_string()).filter(|s| !s.is_empty());
+    }
+
+    let path = source.file_path()?;
+
+    match fs::read_to_string(path) {
+        Ok(session) => Some(session.trim().to_string()).filter(|s| !s.is_empty()),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+        Err(e) => {
+            eprintln!("Failed to read session cookie: {e}");
+            None
+        }
+    }
+}
+
+pub fn get_input_path(puzzle: Puzzle) -> String {
+    puzzle.paths().input(puzzle.day).display().to_string()
+}
+
+pub fn get_puzzle_path(puzzle: Puzzle) -> String {
+    puzzle.paths().puzzle(puzzle.day).display().to_string()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_wait, AocClient, AocClientError, Hint};
+    use crate::{day, template::Puzzle};
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread::{self, JoinHandle},
+        time::Duration,
+    };
+
+    /// Serve one canned response per request on a local port.
+    /// Returns the base URL and a handle that yields the raw requests.
+    fn serve(responses: Vec<(u16, &'static str)>) -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let mut requests = vec![];
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+                let mut request = String::new();
+                let mut content_length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(len) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                        content_length = len.trim().parse().unwrap();
+                    }
+                    request.push_str(&line);
+                    if line == "\r\n" || line.is_empty() {
+                        break;
+                    }
+                }
+                let mut body_buf = vec![0; content_length];
+                reader.read_exact(&mut body_buf).unwrap();
+                request.push_str(&String::from_utf8(body_buf).unwrap());
+                requests.push(request);
+
+                write!(
+                    stream,
+                    "HTTP/1.1 {status} X\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                )
+                .unwrap();
+            }
+            requests
+        });
+
+        (base_url, handle)
+    }
+
+    const PUZZLE: Puzzle = Puzzle {
+        year: 2017,
+        day: day!(1),
+    };
+
+    fn client(base_url: &str) -> AocClient {
+        AocClient::new(base_url, Some("cookie".into()))
+    }
+
+    #[test]
+    fn downloads_input_with_session() {
+        let (base_url, handle) = serve(vec![(200, "1122\n")]);
+        let input = client(&base_url).input(Puzzle::new(2016, day!(3))).unwrap();
+        assert_eq!(input, "1122\n");
+
+        let requests = handle.join().unwrap();
+        assert!(requests[0].starts_with("GET /2016/day/3/input "));
+        assert!(requests[0].contains("session=cookie"));
+    }
+
+    #[test]
+    fn downloads_puzzle_as_markdown() {
+        let (base_url, handle) = serve(vec![(
+            200,
+            "<main><article><h2>--- Day 1: Test ---</h2><p>Hi <em>there</em>.</p></article></main>",
+        )]);
+        let puzzle = client(&base_url).puzzle(PUZZLE).unwrap();
+        assert_eq!(puzzle, "## --- Day 1: Test ---\n\nHi *there*.\n");
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn maps_error_statuses() {
+        let (base_url, handle) = serve(vec![(404, "Not found"), (400, "Please log in"), (500, "")]);
+        let client = client(&base_url);
+        assert!(matches!(
+            client.input(PUZZLE),
+            Err(AocClientError::TooEarly)
+        ));
+        assert!(matches!(
+            client.input(PUZZLE),
+            Err(AocClientError::Unauthorized)
+        ));
+        assert!(matches!(
+            client.input(PUZZLE),
+            Err(AocClientError::BadStatus(500))
+        ));
+        handle.join().unwrap();
+    }
+
+    #[test]
+    fn requires_session() {
+        let client = AocClient::new("http://127.0.0.1:1", None);
+        assert!(matches!(
+            client.input(PUZZLE),
+            Err(AocClientError::MissingSession)
+        ));
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (base_url, handle) = serve(vec![
+            (200, "<article><p>That's the right answer! You are <em>one gold star</em> closer.</p></article>"),
+            (200, "<article><p>That's not the right answer; your answer is too high. Please wait one minute before trying again.</p></article>"),
+            (200, "<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait.</p></article>"),
+            (200, "<article><p>You don't seem to be solving the right level.  Did you already complete it?</p></article>"),
+        ]);
+        let client = client(&base_url);
+
+        assert!(client
+            .submit(PUZZLE, 1, "42")
+            .unwrap()
+            .starts_with("That's the right answer!"));
+
+        assert!(matches!(
+            client.submit(PUZZLE, 1, "43"),
+            Err(AocClientError::WrongAnswer {
+                hint: Some(Hint::TooHigh),
+                wait: Some(wait),
+            }) if wait == Duration::from_secs(60)
+        ));
+
+        assert!(matches!(
+            client.submit(PUZZLE, 1, "44"),
+            Err(AocClientError::RateLimited { wait: Some(wait) }) if wait == Duration::from_secs(65)
+        ));
+
+        assert!(matches!(
+            client.submit(PUZZLE, 2, "45"),
+            Err(AocClientError::WrongLevel)
+        ));
+
+        let requests = handle.join().unwrap();
+        assert!(requests[0].starts_with("POST /2017/day/1/answer "));
+        assert!(requests[0].ends_with("level=1&answer=42"));
+    }
+
+    #[test]
+    fn parses_wait_times() {
+        assert_eq!(
+            parse_wait("You have 34s left to wait."),
+            Some(Duration::from_secs(34))
+        );
+        assert_eq!(
+            parse_wait("Please wait 5 minutes before trying again."),
+            Some(Duration::from_secs(300))
+        );
+        assert_eq!(parse_wait("That's the right answer!"), None);
+    }
+}
@@ -1,5 +1,41 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::process;
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::{
+    all_days, formats,
+    run_multi::{run_multi, Output},
+    Format, Puzzle, Registry, RunLimits, Years,
+};
+
+pub fn handle(
+    registry: &Registry,
+    years: Years,
+    jobs: Option<usize>,
+    limits: RunLimits,
+    format: Format,
+    dashboard: bool,
+) {
+    let puzzles = years
+        .list(registry)
+        .into_iter()
+        .flat_map(|year| all_days().map(move |day| Puzzle::new(year, day)))
+        .collect();
+
+    let summary = run_multi(
+        registry,
+        &puzzles,
+        None,
+        jobs.unwrap_or(1),
+        limits,
+        if dashboard {
+            Output::Dashboard
+        } else {
+            format.output()
+        },
+    );
+
+    print!("{}", format.render(&formats::rows(&summary)));
+
+    if !summary.failed.is_empty() {
+        process::exit(1);
+    }
 }
@@ -1,14 +1,40 @@
-use crate::template::{aoc_cli, Day};
-use std::process;
+use crate::template::{aoc_client, aoc_client::AocClient, Puzzle};
+use std::{fs, process};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+pub fn handle(puzzle: Puzzle) {
+    let client = AocClient::from_env();
+
+    let input_path = aoc_client::get_input_path(puzzle);
+    let puzzle_path = aoc_client::get_puzzle_path(puzzle);
+
+    let downloaded = client
+        .input(puzzle)
+        .and_then(|input| Ok((input, client.puzzle(puzzle)?)));
+
+    let (input, description) = match downloaded {
+        Ok(files) => files,
+        Err(e) => {
+            eprintln!(
+                "failed to download day {} of {}: {e}",
+                puzzle.day, puzzle.year
+            );
+            process::exit(1);
+        }
+    };
+
+    if let Err(e) = puzzle.paths().create_dirs() {
+        eprintln!("failed to create data directories: {e}");
         process::exit(1);
     }
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
-    };
+    for (path, contents) in [(&input_path, input), (&puzzle_path, description)] {
+        if let Err(e) = fs::write(path, contents) {
+            eprintln!("failed to write \"{path}\": {e}");
+            process::exit(1);
+        }
+    }
+
+    println!("---");
+    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
 }
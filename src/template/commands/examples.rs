@@ -0,0 +1,177 @@
+use std::{
+    fs,
+    io::{self, BufRead, Write},
+    process,
+};
+
+use crate::template::{
+    aoc_client,
+    examples::{Example, Manifest},
+    extract::{self, Candidate},
+    Day, Puzzle, ANSI_BOLD, ANSI_RESET,
+};
+
+enum Choice {
+    Accept,
+    Skip,
+    Quit,
+}
+
+/// Propose the examples found in the description of `puzzle` as example files and manifest
+/// entries. Every proposal is confirmed interactively, unless `accept_all` is set.
+pub fn handle(puzzle: Puzzle, accept_all: bool) {
+    let puzzle_path = aoc_client::get_puzzle_path(puzzle);
+
+    let description = match fs::read_to_string(&puzzle_path) {
+        Ok(description) => description,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            eprintln!(
+                "No puzzle description at \"{puzzle_path}\", run `cargo download {}{}` first.",
+                puzzle.day,
+                puzzle.year_flag()
+            );
+            process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("failed to read \"{puzzle_path}\": {e}");
+            process::exit(1);
+        }
+    };
+
+    let mut manifest = match Manifest::read(puzzle) {
+        Ok(manifest) => manifest,
+        Err(e) => {
+            eprintln!(
+                "failed to read \"{}\": {e}",
+                Manifest::path(puzzle).display()
+            );
+            process::exit(1);
+        }
+    };
+
+    let candidates = extract::extract(&description);
+    if candidates.is_empty() {
+        println!("No examples found in \"{puzzle_path}\".");
+        return;
+    }
+
+    let files = file_names(puzzle.day, &candidates);
+    let mut added = vec![];
+
+    for (candidate, file) in candidates.iter().zip(&files) {
+        if manifest.contains(file, candidate.part) {
+            continue;
+        }
+
+        println!(
+            "{ANSI_BOLD}Part {}{ANSI_RESET}: \"{file}\", answer `{}`",
+            candidate.part, candidate.answer
+        );
+        for line in candidate.input.lines() {
+            println!("    {line}");
+        }
+
+        let choice = if accept_all { Choice::Accept } else { prompt() };
+
+        match choice {
+            Choice::Accept => {}
+            Choice::Skip => continue,
+            Choice::Quit => break,
+        }
+
+        if let Err(e) = write_example(puzzle, file, &candidate.input) {
+            eprintln!("Skipped \"{file}\": {e}");
+            continue;
+        }
+
+        let example = Example {
+            file: file.clone(),
+            part: candidate.part,
+            answer: candidate.answer.clone(),
+            params: vec![],
+        };
+        manifest.examples.push(example.clone());
+        added.push(example);
+    }
+
+    if added.is_empty() {
+        println!("---");
+        println!("🎄 No new examples.");
+        return;
+    }
+
+    if let Err(e) = Manifest::append(puzzle, &added) {
+        eprintln!(
+            "failed to write \"{}\": {e}",
+            Manifest::path(puzzle).display()
+        );
+        process::exit(1);
+    }
+
+    println!("---");
+    println!(
+        "🎄 Added {} example(s) to \"{}\".",
+        added.len(),
+        Manifest::path(puzzle).display()
+    );
+    println!(
+        "🎄 Type `cargo test --bin {}` to check them.",
+        puzzle.bin_name()
+    );
+}
+
+/// Name the example files like the template does: `NN.txt` for a single example,
+/// `NN-1.txt`, `NN-2.txt`, ... for several. Candidates with the same input share a file.
+fn file_names(day: Day, candidates: &[Candidate]) -> Vec<String> {
+    let mut inputs: Vec<&str> = vec![];
+    for candidate in candidates {
+        if !inputs.contains(&candidate.input.as_str()) {
+            inputs.push(&candidate.input);
+        }
+    }
+
+    candidates
+        .iter()
+        .map(|candidate| {
+            if inputs.len() == 1 {
+                format!("{day}.txt")
+            } else {
+                let n = inputs.iter().position(|i| *i == candidate.input).unwrap() + 1;
+                format!("{day}-{n}.txt")
+            }
+        })
+        .collect()
+}
+
+/// Write an example file. Files that already have other contents are never overwritten,
+/// empty ones (e.g. created by `scaffold`) are.
+fn write_example(puzzle: Puzzle, file: &str, input: &str) -> Result<(), String> {
+    let path = puzzle.paths().examples.join(file);
+    let contents = format!("{input}\n");
+
+    match fs::read_to_string(&path) {
+        Ok(existing) if existing.trim_end() == input.trim_end() => return Ok(()),
+        Ok(existing) if !existing.trim().is_empty() => {
+            return Err("the file exists and has different contents.".into())
+        }
+        _ => {}
+    }
+
+    fs::write(&path, contents).map_err(|e| e.to_string())
+}
+
+fn prompt() -> Choice {
+    print!("Add this example? [Y/n/q] ");
+    io::stdout().flush().ok();
+
+    let mut answer = String::new();
+    match io::stdin().lock().read_line(&mut answer) {
+        // End of input, nothing more will be confirmed.
+        Ok(0) | Err(_) => Choice::Quit,
+        Ok(_) => match answer.trim().to_lowercase().as_str() {
+            "" | "y" | "yes" => Choice::Accept,
+            "q" | "quit" => Choice::Quit,
+            _ => Choice::Skip,
+        },
+    }
+}
@@ -1,6 +1,10 @@
 pub mod all;
 pub mod download;
+pub mod examples;
 pub mod read;
+pub mod run_day;
 pub mod scaffold;
 pub mod solve;
+pub mod test;
 pub mod time;
+pub mod verify;
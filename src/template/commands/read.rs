@@ -1,15 +1,74 @@
-use std::process;
+use std::{
+    fs,
+    io::{self, IsTerminal},
+    path::Path,
+    process,
+};
 
-use crate::template::{aoc_cli, Day};
+use crate::template::{aoc_client, aoc_client::AocClient, markdown, Puzzle};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+/// Print the description of `puzzle`, or of a single part of it.
+/// The puzzle is read from `data/puzzles` and only downloaded if it is not stored yet.
+pub fn handle(puzzle: Puzzle, part: Option<u8>) {
+    if let Some(part @ (0 | 3..)) = part {
+        eprintln!("Part must be 1 or 2, got {part}.");
         process::exit(1);
     }
 
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
+    let puzzle_path = aoc_client::get_puzzle_path(puzzle);
+
+    let description = if Path::new(&puzzle_path).exists() {
+        match fs::read_to_string(&puzzle_path) {
+            Ok(puzzle) => puzzle,
+            Err(e) => {
+                eprintln!("failed to read \"{puzzle_path}\": {e}");
+                process::exit(1);
+            }
+        }
+    } else {
+        download(puzzle, &puzzle_path)
+    };
+
+    let Some(description) = markdown::select_part(&description, part) else {
+        eprintln!(
+            "\"{puzzle_path}\" does not contain part two yet. \
+            Once part one is solved, run `cargo download {}{}` to update it.",
+            puzzle.day,
+            puzzle.year_flag()
+        );
         process::exit(1);
     };
+
+    // Keep the markdown as is when it is piped somewhere else.
+    if io::stdout().is_terminal() {
+        print!(
+            "{}",
+            markdown::render(description, markdown::terminal_width())
+        );
+    } else {
+        print!("{description}");
+    }
+}
+
+fn download(puzzle: Puzzle, puzzle_path: &str) -> String {
+    let description = match AocClient::from_env().puzzle(puzzle) {
+        Ok(description) => description,
+        Err(e) => {
+            eprintln!(
+                "failed to fetch puzzle for day {} of {}: {e}",
+                puzzle.day, puzzle.year
+            );
+            process::exit(1);
+        }
+    };
+
+    if let Err(e) = puzzle
+        .paths()
+        .create_dirs()
+        .and_then(|()| fs::write(puzzle_path, &description))
+    {
+        eprintln!("failed to write \"{puzzle_path}\": {e}");
+    }
+
+    description
 }
@@ -0,0 +1,25 @@
+use std::process;
+
+use crate::template::{
+    runner::{BenchOptions, RunOptions},
+    Puzzle, Registry,
+};
+
+/// Name of the hidden subcommand that `run_multi` spawns to run a single day in a child process.
+pub const COMMAND: &str = "__run-day";
+
+pub fn handle(registry: &Registry, puzzle: Puzzle, bench: Option<BenchOptions>) {
+    let Some(solution) = registry.get(puzzle) else {
+        eprintln!("Day {} of {} is not solved.", puzzle.day, puzzle.year);
+        process::exit(1);
+    };
+
+    let options = RunOptions {
+        bench,
+        ..RunOptions::default()
+    };
+
+    if !solution.run(&options) {
+        process::exit(1);
+    }
+}
@@ -1,46 +1,118 @@
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::Path,
     process,
 };
 
-use crate::template::Day;
+use crate::template::{
+    aoc_client, config,
+    examples::Manifest,
+    markdown,
+    templates::{self, Placeholders},
+    unix_now, Puzzle,
+};
+
+const MANIFEST_TEMPLATE: &str = r#"# Examples of day %DAY%, checked by `cargo test --bin %BIN%`.
+# Add an entry for every example file, `file` defaults to "%DAY%.txt".
+#
+# [[example]]
+# file = "%DAY%.txt"
+# part = 1
+# answer = 0
+"#;
 
-const MODULE_TEMPLATE: &str =
-    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+#[derive(Clone, Debug, Default)]
+pub struct ScaffoldOptions {
+    /// Name of the template, defaults to `scaffold.template` in `aoc.toml`.
+    pub template: Option<String>,
+    /// Type of the answers, defaults to `scaffold.answer_type` in `aoc.toml`.
+    pub answer_type: Option<String>,
+    /// Replace an existing solution, after backing it up.
+    pub force: bool,
+}
 
 fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
     OpenOptions::new().write(true).create_new(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(path)
+/// Create a file with `contents`, unless it exists. Returns whether the file was created.
+fn create_missing_file(path: &str, contents: &str) -> Result<bool, std::io::Error> {
+    match safe_create_file(path) {
+        Ok(mut file) => file.write_all(contents.as_bytes()).map(|()| true),
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => Ok(false),
+        Err(e) => Err(e),
+    }
 }
 
-pub fn handle(day: Day) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+pub fn handle(puzzle: Puzzle, options: &ScaffoldOptions) {
+    let config = config::get();
+    let paths = puzzle.paths();
+    let input_path = aoc_client::get_input_path(puzzle);
+    let example_path = paths.example(puzzle.day, None).display().to_string();
+    let manifest_path = Manifest::path(puzzle);
+    let module_path = format!("src/bin/{}.rs", puzzle.bin_name());
+
+    let template_name = options
+        .template
+        .as_deref()
+        .unwrap_or(&config.scaffold.template);
+    let template = match templates::load(template_name) {
+        Ok(template) => template,
+        Err(e) => {
+            eprintln!("Failed to load template: {e}");
+            process::exit(1);
+        }
+    };
+
+    let title = fs::read_to_string(aoc_client::get_puzzle_path(puzzle))
+        .ok()
+        .and_then(|puzzle| markdown::puzzle_title(&puzzle).map(String::from));
+
+    let module = templates::render(
+        &template,
+        &Placeholders {
+            puzzle,
+            title,
+            answer_type: options
+                .answer_type
+                .clone()
+                .unwrap_or_else(|| config.scaffold.answer_type.clone()),
+        },
+    );
+
+    if options.force && Path::new(&module_path).exists() {
+        let backup_path = format!("{module_path}.{}.bak", unix_now());
+        match fs::rename(&module_path, &backup_path) {
+            Ok(()) => println!("Backed up module file to \"{backup_path}\""),
+            Err(e) => {
+                eprintln!("Failed to back up module file: {e}");
+                process::exit(1);
+            }
+        }
+    }
 
     let mut file = match safe_create_file(&module_path) {
         Ok(file) => file,
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+            eprintln!(
+                "Failed to create module file: \"{module_path}\" already exists. \
+                Use `--force` to replace it, a backup is kept."
+            );
+            process::exit(1);
+        }
         Err(e) => {
             eprintln!("Failed to create module file: {e}");
             process::exit(1);
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
+    match file.write_all(module.as_bytes()) {
         Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
+            println!(
+                "Created module file \"{}\" from template `{template_name}`",
+                &module_path
+            );
         }
         Err(e) => {
             eprintln!("Failed to write module contents: {e}");
@@ -48,26 +120,48 @@ pub fn handle(day: Day) {
         }
     }
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
+    if let Err(e) = paths.create_dirs() {
+        eprintln!("Failed to create data directories: {e}");
+        process::exit(1);
+    }
+
+    // Inputs and examples are kept if they exist, so that `--force` only replaces the module.
+    match create_missing_file(&input_path, "") {
+        Ok(true) => println!("Created empty input file \"{}\"", &input_path),
+        Ok(false) => {}
         Err(e) => {
             eprintln!("Failed to create input file: {e}");
             process::exit(1);
         }
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
+    match create_missing_file(&example_path, "") {
+        Ok(true) => println!("Created empty example file \"{}\"", &example_path),
+        Ok(false) => {}
         Err(e) => {
             eprintln!("Failed to create example file: {e}");
             process::exit(1);
         }
     }
 
+    match create_missing_file(
+        &manifest_path.to_string_lossy(),
+        &MANIFEST_TEMPLATE
+            .replace("%DAY%", &puzzle.day.to_string())
+            .replace("%BIN%", &puzzle.bin_name()),
+    ) {
+        Ok(true) => println!("Created example manifest \"{}\"", manifest_path.display()),
+        Ok(false) => {}
+        Err(e) => {
+            eprintln!("Failed to create example manifest: {e}");
+            process::exit(1);
+        }
+    }
+
     println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    println!(
+        "🎄 Type `cargo solve {}{}` to run your solution.",
+        puzzle.day,
+        puzzle.year_flag()
+    );
 }
@@ -1,28 +1,89 @@
-use std::process::{Command, Stdio};
+use std::{
+    env, fs,
+    process::{self, Command, Stdio},
+};
 
-use crate::template::Day;
+use crate::template::{
+    commands::test,
+    params::Param,
+    report::{self, REPORT_FILE_ENV},
+    runner::{BenchOptions, InputSource},
+    watch, Puzzle,
+};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+/// How the solution is built.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct BuildOptions {
+    pub release: bool,
+    /// Profile the heap with DHAT, takes precedence over `release` and `alloc_stats`.
+    pub dhat: bool,
+    /// Count the allocations of every part.
+    pub alloc_stats: bool,
+}
+
+/// Arguments of `cargo run` for `puzzle`, up to the input and parameters of the solution.
+fn run_args(puzzle: Puzzle, build: BuildOptions, bench: Option<&BenchOptions>) -> Vec<String> {
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), puzzle.bin_name()];
 
-    if dhat {
+    if build.dhat {
         cmd_args.extend([
             "--profile".to_string(),
             "dhat".to_string(),
             "--features".to_string(),
             "dhat-heap".to_string(),
         ]);
-    } else if release {
-        cmd_args.push("--release".to_string());
+    } else {
+        if build.release {
+            cmd_args.push("--release".to_string());
+        }
+        if build.alloc_stats {
+            cmd_args.extend(["--features".to_string(), "alloc-stats".to_string()]);
+        }
     }
 
     cmd_args.push("--".to_string());
 
+    if let Some(bench) = bench {
+        cmd_args.push("--time".to_string());
+        cmd_args.extend(bench.to_args());
+    }
+
+    cmd_args
+}
+
+fn input_args(input: &InputSource, params: &[Param]) -> Vec<String> {
+    let mut cmd_args = input.to_args();
+
+    for param in params {
+        cmd_args.push("--param".to_string());
+        cmd_args.push(param.to_string());
+    }
+
+    cmd_args
+}
+
+pub fn handle(
+    puzzle: Puzzle,
+    build: BuildOptions,
+    bench: Option<&BenchOptions>,
+    submit_part: Option<u8>,
+    accept: bool,
+    input: &InputSource,
+    params: &[Param],
+) {
+    let mut cmd_args = run_args(puzzle, build, bench);
+
     if let Some(submit_part) = submit_part {
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string());
     }
 
+    if accept {
+        cmd_args.push("--accept".to_string());
+    }
+
+    cmd_args.extend(input_args(input, params));
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
@@ -30,5 +91,55 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         .spawn()
         .unwrap();
 
-    cmd.wait().unwrap();
+    let status = cmd.wait().unwrap();
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
+    }
+}
+
+/// Rebuild and rerun the solution and its example tests whenever one of its files changes, and
+/// compare the answers with the previous run.
+pub fn watch(
+    puzzle: Puzzle,
+    build: BuildOptions,
+    bench: Option<&BenchOptions>,
+    input: &InputSource,
+    params: &[Param],
+) {
+    let report_path = env::temp_dir().join(format!("aoc-watch-{}-{puzzle}.jsonl", process::id()));
+    let mut cmd_args = run_args(puzzle, build, bench);
+    cmd_args.extend(input_args(input, params));
+
+    let mut previous_reports = None;
+    let mut previous_tests = None;
+
+    watch::watch(puzzle, input, |changed| {
+        watch::print_header(puzzle, changed);
+
+        let _ = fs::remove_file(&report_path);
+        if let Err(e) = Command::new("cargo")
+            .args(&cmd_args)
+            .env(REPORT_FILE_ENV, &report_path)
+            .status()
+        {
+            eprintln!("Failed to run solution: {e}");
+        }
+
+        let reports = report::read_file(&report_path).unwrap_or_else(|e| {
+            eprintln!("Failed to read answers: {e}");
+            vec![]
+        });
+
+        println!();
+        let passed = test::run(puzzle, build.release);
+
+        watch::print_answers(previous_reports.as_deref(), &reports);
+        watch::print_tests(previous_tests, passed);
+
+        // a failed build has no answers, compare the next run with the last one that had some.
+        if !reports.is_empty() {
+            previous_reports = Some(reports);
+        }
+        previous_tests = Some(passed);
+    });
 }
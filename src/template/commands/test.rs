@@ -0,0 +1,38 @@
+use std::process::{self, Command};
+
+use crate::template::{runner::InputSource, watch, Puzzle};
+
+pub fn handle(puzzle: Puzzle, release: bool, watch: bool) {
+    if !watch {
+        if !run(puzzle, release) {
+            process::exit(1);
+        }
+        return;
+    }
+
+    let mut previous = None;
+    watch::watch(puzzle, &InputSource::Puzzle, |changed| {
+        watch::print_header(puzzle, changed);
+        let passed = run(puzzle, release);
+        println!();
+        watch::print_tests(previous, passed);
+        previous = Some(passed);
+    });
+}
+
+/// Run the unit and example tests of `puzzle`'s binary. Returns whether they passed.
+pub fn run(puzzle: Puzzle, release: bool) -> bool {
+    let mut cmd = Command::new("cargo");
+    cmd.args(["test", "--bin", &puzzle.bin_name()]);
+    if release {
+        cmd.arg("--release");
+    }
+
+    match cmd.status() {
+        Ok(status) => status.success(),
+        Err(e) => {
+            eprintln!("Failed to run tests: {e}");
+            false
+        }
+    }
+}
@@ -1,40 +1,239 @@
-use std::collections::HashSet;
+use std::{
+    collections::{BTreeMap, HashSet},
+    io::Write,
+    process,
+};
 
-use crate::template::run_multi::run_multi;
+use crate::template::history::{self, Baseline, Delta, History, HistoryEntry};
+use crate::template::report::PARSE_PART;
+use crate::template::run_multi::{run_multi, Output};
+use crate::template::runner::BenchOptions;
+use crate::template::stats::format_nanos;
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::{
+    all_days, formats, readme_benchmarks, Day, Format, Puzzle, Registry, RunLimits, Years,
+    ANSI_BOLD, ANSI_RESET,
+};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+/// Regression threshold in percent, if `--threshold` is not passed.
+pub const DEFAULT_THRESHOLD: f64 = 10.0;
 
-    let days_to_run = day.map_or_else(
-        || {
-            if run_all {
-                all_days().collect()
-            } else {
-                // when the `--all` flag is not set, filter out days that are fully benched.
-                all_days()
-                    .filter(|day| !stored_timings.is_day_complete(*day))
-                    .collect()
+/// Compare a run with a baseline from the benchmark history.
+pub struct CompareArgs {
+    /// `latest`, or a git ref.
+    pub reference: String,
+    /// Parts whose median got slower by more than this many percent are flagged.
+    pub threshold: f64,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    registry: &Registry,
+    years: Years,
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    bench: BenchOptions,
+    jobs: Option<usize>,
+    limits: RunLimits,
+    compare: Option<CompareArgs>,
+    format: Format,
+    dashboard: bool,
+) {
+    let years = years.list(registry);
+    let with_year = years.len() > 1;
+
+    // with `--store`, the readme lists every year, including those that don't run.
+    let stored_years: Vec<u16> = if store {
+        let mut stored_years = Years::All.list(registry);
+        stored_years.extend(&years);
+        stored_years.sort_unstable();
+        stored_years.dedup();
+        stored_years
+    } else {
+        years.clone()
+    };
+
+    let mut stored_timings: BTreeMap<u16, Timings> = BTreeMap::new();
+    for &year in &stored_years {
+        match Timings::read_from_file(year) {
+            Ok(timings) => stored_timings.insert(year, timings),
+            // storing would replace the timings that could not be read.
+            Err(e) if store => {
+                eprintln!("Failed to read stored timings of {year}: {e}");
+                process::exit(1);
+            }
+            Err(e) => {
+                eprintln!("Failed to read stored timings of {year}, ignoring them: {e}");
+                stored_timings.insert(year, Timings::default())
+            }
+        };
+    }
+
+    let mut histories: BTreeMap<u16, History> = BTreeMap::new();
+    for &year in &years {
+        match History::read_from_file(year) {
+            Ok(history) => histories.insert(year, history),
+            Err(e) => {
+                eprintln!("Failed to read benchmark history: {e}");
+                process::exit(1);
             }
+        };
+    }
+
+    let baseline = compare
+        .as_ref()
+        .map(|args| match Baseline::parse(&args.reference) {
+            Ok(baseline) => baseline,
+            Err(e) => {
+                eprintln!("{e}");
+                process::exit(1);
+            }
+        });
+
+    let puzzles_to_run: HashSet<Puzzle> = years
+        .iter()
+        .flat_map(|&year| {
+            let stored = &stored_timings[&year];
+            all_days()
+                .filter(move |d| day.is_none() || day == Some(*d))
+                .map(move |d| Puzzle::new(year, d))
+                // when the `--all` flag is not set, filter out days that are fully benched.
+                .filter(move |p| {
+                    day.is_some() || run_all || !stored.is_day_complete(p.day, registry.parts(*p))
+                })
+        })
+        .collect();
+
+    // benchmarks run sequentially by default so they don't interfere with each other.
+    let summary = run_multi(
+        registry,
+        &puzzles_to_run,
+        Some(bench),
+        jobs.unwrap_or(1),
+        limits,
+        if dashboard {
+            Output::Dashboard
+        } else {
+            format.output()
         },
-        |day| HashSet::from([day]),
     );
+    let mut out = format.output().writer();
+
+    let baseline_timings: Option<BTreeMap<u16, Timings>> = baseline.as_ref().map(|b| {
+        histories
+            .iter()
+            .map(|(year, history)| (*year, history.baseline(b)))
+            .collect()
+    });
+    let mut regressed = false;
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    if let (Some(args), Some(baseline), Some(baseline_timings)) =
+        (&compare, &baseline, &baseline_timings)
+    {
+        regressed = print_comparison(
+            &mut out,
+            baseline,
+            baseline_timings,
+            &summary.timings,
+            args.threshold,
+            with_year,
+        );
+    }
 
     if store {
-        let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        for (year, timings) in &summary.timings {
+            let merged_timings = stored_timings[year].merge(timings);
+            merged_timings.store_file(*year).unwrap();
+            stored_timings.insert(*year, merged_timings);
+
+            let history = histories.get_mut(year).unwrap();
+            history.entries.push(HistoryEntry::now(timings.clone()));
+            if let Err(e) = history.store_file(*year) {
+                eprintln!("Failed to store benchmark history: {e}");
+            }
+        }
 
-        println!();
-        match readme_benchmarks::update(merged_timings) {
+        writeln!(out).unwrap();
+        match readme_benchmarks::update(&stored_timings, baseline_timings.as_ref(), registry) {
             Ok(()) => {
-                println!("Stored updated benchmarks.");
+                writeln!(out, "Stored updated benchmarks.").unwrap();
             }
             Err(_) => {
                 eprintln!("Failed to store updated benchmarks.");
             }
         }
     }
+
+    print!("{}", format.render(&formats::rows(&summary)));
+
+    if !summary.failed.is_empty() || regressed {
+        process::exit(1);
+    }
+}
+
+/// Print the change of every part's median, returns `true` if any part regressed.
+fn print_comparison(
+    out: &mut dyn Write,
+    baseline: &Baseline,
+    baseline_timings: &BTreeMap<u16, Timings>,
+    timings: &BTreeMap<u16, Timings>,
+    threshold: f64,
+    with_year: bool,
+) -> bool {
+    let deltas: Vec<(Puzzle, Delta)> = timings
+        .iter()
+        .filter_map(|(year, timings)| Some((*year, baseline_timings.get(year)?, timings)))
+        .flat_map(|(year, baseline_timings, timings)| {
+            history::compare(baseline_timings, timings)
+                .into_iter()
+                .map(move |delta| (Puzzle::new(year, delta.day), delta))
+        })
+        .collect();
+
+    writeln!(
+        out,
+        "\n{ANSI_BOLD}Compare{ANSI_RESET} (baseline: {baseline}, threshold: {threshold}%)"
+    )
+    .unwrap();
+    writeln!(out, "-------").unwrap();
+
+    if deltas.is_empty() {
+        writeln!(out, "No stored timings to compare with.").unwrap();
+        return false;
+    }
+
+    let mut regressed: Vec<Puzzle> = vec![];
+
+    for (puzzle, delta) in &deltas {
+        let part = if delta.part == PARSE_PART {
+            "parse".to_string()
+        } else {
+            format!("part {}", delta.part)
+        };
+
+        let is_regression = delta.is_regression(threshold);
+        if is_regression && !regressed.contains(puzzle) {
+            regressed.push(*puzzle);
+        }
+
+        writeln!(
+            out,
+            "{} {part}: {} → {} ({:+.1}%){}",
+            puzzle.label(with_year),
+            format_nanos(delta.before),
+            format_nanos(delta.after),
+            delta.percent(),
+            if is_regression { " ✖ regressed" } else { "" }
+        )
+        .unwrap();
+    }
+
+    if regressed.is_empty() {
+        false
+    } else {
+        let labels: Vec<String> = regressed.iter().map(|p| p.label(with_year)).collect();
+        eprintln!("\n{ANSI_BOLD}Regressed:{ANSI_RESET} {}", labels.join(", "));
+        true
+    }
 }
@@ -0,0 +1,125 @@
+use std::{collections::HashSet, io::Write, process};
+
+use crate::template::{
+    all_days,
+    answers::{self, Answers, Verdict},
+    formats,
+    report::PARSE_PART,
+    run_multi::{run_multi, Output},
+    Day, Format, Puzzle, Registry, RunLimits, Years, ANSI_BOLD, ANSI_RESET,
+};
+
+pub fn handle(
+    registry: &Registry,
+    years: Years,
+    day: Option<Day>,
+    jobs: Option<usize>,
+    limits: RunLimits,
+    format: Format,
+    dashboard: bool,
+) {
+    let years = years.list(registry);
+
+    let mut known_answers = vec![];
+    for &year in &years {
+        match Answers::read_from_file(year) {
+            Ok(answers) => known_answers.push((year, answers)),
+            Err(e) => {
+                eprintln!("Failed to read known answers of {year}: {e}");
+                process::exit(1);
+            }
+        }
+    }
+
+    let days_to_run: Vec<Day> = day.map_or_else(|| all_days().collect(), |day| vec![day]);
+    let puzzles: HashSet<Puzzle> = years
+        .iter()
+        .flat_map(|&year| days_to_run.iter().map(move |&day| Puzzle::new(year, day)))
+        .collect();
+
+    let summary = run_multi(
+        registry,
+        &puzzles,
+        None,
+        jobs.unwrap_or(1),
+        limits,
+        if dashboard {
+            Output::Dashboard
+        } else {
+            format.output()
+        },
+    );
+
+    let mut checks = vec![];
+    for (year, answers) in &known_answers {
+        let days: Vec<(Day, &[u8])> = days_to_run
+            .iter()
+            .map(|&d| (d, registry.parts(Puzzle::new(*year, d))))
+            .collect();
+        let reports = summary.reports.get(year).map_or(&[][..], Vec::as_slice);
+
+        checks.extend(
+            answers::verify(answers, reports, &days)
+                .into_iter()
+                .map(|check| (Puzzle::new(*year, check.day), check)),
+        );
+    }
+    let with_year = years.len() > 1;
+    let mut out = format.output().writer();
+
+    writeln!(out, "\n{ANSI_BOLD}Verify{ANSI_RESET}").unwrap();
+    writeln!(out, "------").unwrap();
+
+    let (mut correct, mut mismatched, mut failed, mut missing) = (0, 0, 0, 0);
+
+    for (puzzle, check) in &checks {
+        let name = if check.part == PARSE_PART {
+            format!("{} parse", puzzle.label(with_year))
+        } else {
+            format!("{} part {}", puzzle.label(with_year), check.part)
+        };
+
+        match &check.verdict {
+            Verdict::Correct => correct += 1,
+            Verdict::Mismatch { expected, actual } => {
+                mismatched += 1;
+                match actual {
+                    Some(actual) => {
+                        writeln!(out, "✖ {name}: expected `{expected}`, got `{actual}`").unwrap();
+                    }
+                    None => {
+                        writeln!(out, "✖ {name}: expected `{expected}`, got no answer").unwrap();
+                    }
+                }
+            }
+            Verdict::Failed { error } => {
+                failed += 1;
+                match error {
+                    Some(error) => {
+                        let error = error.lines().next().unwrap_or("");
+                        writeln!(out, "✖ {name}: failed: {error}").unwrap();
+                    }
+                    None => writeln!(out, "✖ {name}: failed").unwrap(),
+                }
+            }
+            Verdict::Missing { actual } => {
+                missing += 1;
+                writeln!(out, "? {name}: no known answer for `{actual}`").unwrap();
+            }
+        }
+    }
+
+    writeln!(
+        out,
+        "{correct} correct, {mismatched} mismatched, {failed} failed, {missing} without known answer"
+    )
+    .unwrap();
+
+    let mut rows = formats::rows(&summary);
+    formats::apply_checks(&mut rows, &checks);
+    print!("{}", format.render(&rows));
+
+    if checks.iter().any(|(_, c)| c.verdict.is_failure()) || !summary.failed.is_empty() {
+        process::exit(1);
+    }
+}
@@ -0,0 +1,514 @@
+/// Project configuration, stored in `aoc.toml`.
+///
+/// Every key is optional, a missing file or key falls back to the defaults below. Command line
+/// flags, like `--budget` or `--template`, take precedence over the file.
+///
+/// ```toml
+/// year = 2017                  # the primary year, defaults to the latest event
+///
+/// [paths]                      # of the primary year, other years use `<data>/<year>/...`
+/// data = "data"                # timings, answers, benchmark history and submissions
+/// inputs = "data/inputs"       # defaults to `<data>/inputs`
+/// examples = "data/examples"   # defaults to `<data>/examples`
+/// puzzles = "data/puzzles"     # defaults to `<data>/puzzles`
+///
+/// [session]
+/// env = "AOC_SESSION"
+/// file = "~/.adventofcode.session"
+///
+/// [bench]
+/// budget = 1000                # milliseconds, or `samples = 100`
+/// warmup = 1
+/// reject_outliers = false
+///
+/// [limits]                     # of a single day in `all`, `time` and `verify`, unlimited by default
+/// # timeout = 60               # seconds
+/// # memory = 4096              # MiB, only enforced on Linux
+///
+/// [scaffold]
+/// template = "default"
+/// answer_type = "u32"
+///
+/// [readme]
+/// path = "README.md"
+/// marker = "<!--- benchmarking table --->"
+/// ```
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+    process,
+    sync::OnceLock,
+    time::Duration,
+};
+use toml::{Table, Value};
+
+use crate::template::run_multi::RunLimits;
+use crate::template::runner::{BenchLimit, BenchOptions};
+use crate::template::templates::DEFAULT_TEMPLATE;
+use crate::template::{unix_now, Day, ANSI_BOLD, ANSI_RESET};
+
+pub const CONFIG_FILE_PATH: &str = "aoc.toml";
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Config {
+    /// The primary year. Commands use it unless `--year` is passed.
+    pub year: u16,
+    pub paths: Paths,
+    pub session: SessionSource,
+    /// Defaults for benchmarks, overridden by `--samples`, `--budget`, `--warmup` and
+    /// `--reject-outliers`.
+    pub bench: BenchOptions,
+    /// Defaults for the limits of a day, overridden by `--timeout` and `--max-memory`.
+    pub limits: RunLimits,
+    pub scaffold: ScaffoldDefaults,
+    pub readme: ReadmeConfig,
+}
+
+/// Where the files of the template are stored.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Paths {
+    pub data: PathBuf,
+    pub inputs: PathBuf,
+    pub examples: PathBuf,
+    pub puzzles: PathBuf,
+}
+
+/// Where the session cookie is read from: the environment variable `env`, or else `file`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SessionSource {
+    pub env: String,
+    /// A leading `~` is replaced with the home directory.
+    pub file: String,
+}
+
+/// Defaults of `cargo scaffold`, overridden by `--template` and `--answer-type`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScaffoldDefaults {
+    pub template: String,
+    pub answer_type: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReadmeConfig {
+    pub path: PathBuf,
+    /// Marker before and after the benchmark table.
+    pub marker: String,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            year: latest_event_year(),
+            paths: Paths::new("data".into()),
+            session: SessionSource {
+                env: "AOC_SESSION".into(),
+                file: "~/.adventofcode.session".into(),
+            },
+            bench: BenchOptions::default(),
+            limits: RunLimits::default(),
+            scaffold: ScaffoldDefaults {
+                template: DEFAULT_TEMPLATE.into(),
+                answer_type: "u32".into(),
+            },
+            readme: ReadmeConfig {
+                path: "README.md".into(),
+                marker: "<!--- benchmarking table --->".into(),
+            },
+        }
+    }
+}
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+/// The configuration of the project, read from `aoc.toml` on first use.
+/// Exits the process if the file is malformed.
+pub fn get() -> &'static Config {
+    CONFIG.get_or_init(|| match Config::read() {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("{ANSI_BOLD}Error:{ANSI_RESET} failed to read \"{CONFIG_FILE_PATH}\": {e}");
+            process::exit(1);
+        }
+    })
+}
+
+impl Config {
+    /// Read `aoc.toml`. If not present, returns the default configuration.
+    pub fn read() -> Result<Self, String> {
+        match fs::read_to_string(CONFIG_FILE_PATH) {
+            Ok(s) => Self::parse(&s),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    pub fn parse(s: &str) -> Result<Self, String> {
+        let table: Table = s
+            .parse()
+            .map_err(|e: toml::de::Error| format!("not a valid TOML file: {}", e.message()))?;
+
+        check_keys(
+            &table,
+            "",
+            &[
+                "year", "paths", "session", "bench", "limits", "scaffold", "readme",
+            ],
+        )?;
+
+        let mut config = Self::default();
+
+        if let Some(year) = integer(&table, "", "year")? {
+            config.year = u16::try_from(year).map_err(|_| "expected `year` to be a year.")?;
+        }
+
+        if let Some(paths) = section(&table, "paths")? {
+            check_keys(paths, "paths", &["data", "inputs", "examples", "puzzles"])?;
+            if let Some(data) = string(paths, "paths", "data")? {
+                config.paths = Paths::new(data.into());
+            }
+            for (key, path) in [
+                ("inputs", &mut config.paths.inputs),
+                ("examples", &mut config.paths.examples),
+                ("puzzles", &mut config.paths.puzzles),
+            ] {
+                if let Some(value) = string(paths, "paths", key)? {
+                    *path = value.into();
+                }
+            }
+        }
+
+        if let Some(session) = section(&table, "session")? {
+            check_keys(session, "session", &["env", "file"])?;
+            if let Some(env) = string(session, "session", "env")? {
+                config.session.env = env;
+            }
+            if let Some(file) = string(session, "session", "file")? {
+                config.session.file = file;
+            }
+        }
+
+        if let Some(bench) = section(&table, "bench")? {
+            check_keys(
+                bench,
+                "bench",
+                &["budget", "samples", "warmup", "reject_outliers"],
+            )?;
+            let count = |key| match integer(bench, "bench", key)? {
+                Some(n) => usize::try_from(n)
+                    .map(Some)
+                    .map_err(|_| format!("expected `bench.{key}` to be positive.")),
+                None => Ok(None),
+            };
+
+            match (count("samples")?, count("budget")?) {
+                (Some(_), Some(_)) => {
+                    return Err("`bench.samples` and `bench.budget` are mutually exclusive.".into())
+                }
+                (Some(samples), None) => config.bench.limit = BenchLimit::Samples(samples.max(1)),
+                (None, Some(millis)) => {
+                    config.bench.limit = BenchLimit::Budget(Duration::from_millis(millis as u64));
+                }
+                (None, None) => {}
+            }
+            if let Some(warmup) = count("warmup")? {
+                config.bench.warmup = warmup;
+            }
+            if let Some(reject_outliers) = boolean(bench, "bench", "reject_outliers")? {
+                config.bench.reject_outliers = reject_outliers;
+            }
+        }
+
+        if let Some(limits) = section(&table, "limits")? {
+            check_keys(limits, "limits", &["timeout", "memory"])?;
+            let positive = |key| match integer(limits, "limits", key)? {
+                Some(n) => u64::try_from(n)
+                    .map(Some)
+                    .map_err(|_| format!("expected `limits.{key}` to be positive.")),
+                None => Ok(None),
+            };
+
+            if let Some(seconds) = positive("timeout")? {
+                config.limits.timeout = Some(Duration::from_secs(seconds));
+            }
+            if let Some(mib) = positive("memory")? {
+                config.limits.memory = Some(mib * 1024 * 1024);
+            }
+        }
+
+        if let Some(scaffold) = section(&table, "scaffold")? {
+            check_keys(scaffold, "scaffold", &["template", "answer_type"])?;
+            if let Some(template) = string(scaffold, "scaffold", "template")? {
+                config.scaffold.template = template;
+            }
+            if let Some(answer_type) = string(scaffold, "scaffold", "answer_type")? {
+                config.scaffold.answer_type = answer_type;
+            }
+        }
+
+        if let Some(readme) = section(&table, "readme")? {
+            check_keys(readme, "readme", &["path", "marker"])?;
+            if let Some(path) = string(readme, "readme", "path")? {
+                config.readme.path = path.into();
+            }
+            if let Some(marker) = string(readme, "readme", "marker")? {
+                config.readme.marker = marker;
+            }
+        }
+
+        Ok(config)
+    }
+
+    /// The paths of `year`. The primary year uses [`Config::paths`], other years the same layout
+    /// inside of `<data>/<year>`.
+    pub fn paths_for(&self, year: u16) -> Paths {
+        if year == self.year {
+            self.paths.clone()
+        } else {
+            Paths::new(self.paths.data.join(year.to_string()))
+        }
+    }
+}
+
+impl Paths {
+    /// Paths with all directories inside of `data`.
+    fn new(data: PathBuf) -> Self {
+        Self {
+            inputs: data.join("inputs"),
+            examples: data.join("examples"),
+            puzzles: data.join("puzzles"),
+            data,
+        }
+    }
+
+    pub fn input(&self, day: Day) -> PathBuf {
+        self.inputs.join(format!("{day}.txt"))
+    }
+
+    /// The example `NN.txt`, or `NN-<n>.txt` if a number is given.
+    pub fn example(&self, day: Day, n: Option<u8>) -> PathBuf {
+        match n {
+            Some(n) => self.examples.join(format!("{day}-{n}.txt")),
+            None => self.examples.join(format!("{day}.txt")),
+        }
+    }
+
+    pub fn manifest(&self, day: Day) -> PathBuf {
+        self.examples.join(format!("{day}.toml"))
+    }
+
+    pub fn puzzle(&self, day: Day) -> PathBuf {
+        self.puzzles.join(format!("{day}.md"))
+    }
+
+    /// A file in the data directory, e.g. `timings.json`.
+    pub fn data_file(&self, name: &str) -> PathBuf {
+        self.data.join(name)
+    }
+
+    /// Create a file in the data directory, creating the directory first if needed.
+    pub fn create_data_file(&self, name: &str) -> io::Result<fs::File> {
+        fs::create_dir_all(&self.data)?;
+        fs::File::create(self.data_file(name))
+    }
+
+    /// Create all directories, e.g. before the first files of a new year are written.
+    pub fn create_dirs(&self) -> io::Result<()> {
+        for dir in [&self.data, &self.inputs, &self.examples, &self.puzzles] {
+            fs::create_dir_all(dir)?;
+        }
+        Ok(())
+    }
+}
+
+impl SessionSource {
+    /// The session file, with a leading `~` replaced by the home directory.
+    pub fn file_path(&self) -> Option<PathBuf> {
+        match self.file.strip_prefix("~/") {
+            Some(rest) => env::var_os("HOME")
+                .or_else(|| env::var_os("USERPROFILE"))
+                .map(|home| Path::new(&home).join(rest)),
+            None => Some(PathBuf::from(&self.file)),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The year of the latest event: the current year from December on, the previous year before.
+fn latest_event_year() -> u16 {
+    // civil date from days since the epoch, see http://howardhinnant.github.io/date_algorithms.html
+    let days = unix_now() / 86_400 + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    // months are counted from March, so December is 9.
+    let month = (5 * day_of_year + 2) / 153;
+    let year = era * 400 + year_of_era + u64::from(month >= 10);
+
+    u16::try_from(if month == 9 { year } else { year - 1 }).unwrap_or(u16::MAX)
+}
+
+fn qualified(section: &str, key: &str) -> String {
+    if section.is_empty() {
+        key.into()
+    } else {
+        format!("{section}.{key}")
+    }
+}
+
+fn check_keys(table: &Table, section: &str, allowed: &[&str]) -> Result<(), String> {
+    match table.keys().find(|k| !allowed.contains(&k.as_str())) {
+        Some(key) => Err(format!("unknown key `{}`.", qualified(section, key))),
+        None => Ok(()),
+    }
+}
+
+fn section<'a>(table: &'a Table, key: &str) -> Result<Option<&'a Table>, String> {
+    match table.get(key) {
+        None => Ok(None),
+        Some(Value::Table(section)) => Ok(Some(section)),
+        Some(_) => Err(format!("expected `{key}` to be a table.")),
+    }
+}
+
+fn string(table: &Table, section: &str, key: &str) -> Result<Option<String>, String> {
+    match table.get(key) {
+        None => Ok(None),
+        Some(Value::String(s)) => Ok(Some(s.clone())),
+        Some(_) => Err(format!(
+            "expected `{}` to be a string.",
+            qualified(section, key)
+        )),
+    }
+}
+
+fn integer(table: &Table, section: &str, key: &str) -> Result<Option<i64>, String> {
+    match table.get(key) {
+        None => Ok(None),
+        Some(Value::Integer(n)) => Ok(Some(*n)),
+        Some(_) => Err(format!(
+            "expected `{}` to be an integer.",
+            qualified(section, key)
+        )),
+    }
+}
+
+fn boolean(table: &Table, section: &str, key: &str) -> Result<Option<bool>, String> {
+    match table.get(key) {
+        None => Ok(None),
+        Some(Value::Boolean(b)) => Ok(Some(*b)),
+        Some(_) => Err(format!(
+            "expected `{}` to be a boolean.",
+            qualified(section, key)
+        )),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{path::PathBuf, time::Duration};
+
+    use super::Config;
+    use crate::{day, template::runner::BenchLimit};
+
+    #[test]
+    fn parses_config() {
+        let config = Config::parse(
+            r#"
+            year = 2017
+
+            [paths]
+            data = "aoc"
+            puzzles = "descriptions"
+
+            [session]
+            file = "/tmp/session"
+
+            [bench]
+            samples = 10
+            reject_outliers = true
+
+            [limits]
+            timeout = 30
+
+            [scaffold]
+            answer_type = "u64"
+
+            [readme]
+            marker = "<!-- timings -->"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.year, 2017);
+        assert_eq!(
+            config.paths.input(day!(1)),
+            PathBuf::from("aoc/inputs/01.txt")
+        );
+        assert_eq!(
+            config.paths.example(day!(1), Some(2)),
+            PathBuf::from("aoc/examples/01-2.txt")
+        );
+        assert_eq!(
+            config.paths.puzzle(day!(1)),
+            PathBuf::from("descriptions/01.md")
+        );
+        assert_eq!(
+            config.paths.data_file("timings.json"),
+            PathBuf::from("aoc/timings.json")
+        );
+        assert_eq!(config.paths_for(2017), config.paths);
+        assert_eq!(
+            config.paths_for(2016).input(day!(1)),
+            PathBuf::from("aoc/2016/inputs/01.txt")
+        );
+        assert_eq!(config.session.env, "AOC_SESSION");
+        assert_eq!(config.session.file_path(), Some("/tmp/session".into()));
+        assert_eq!(config.bench.limit, BenchLimit::Samples(10));
+        assert_eq!(config.bench.warmup, 1);
+        assert!(config.bench.reject_outliers);
+        assert_eq!(config.limits.timeout, Some(Duration::from_secs(30)));
+        assert_eq!(config.limits.memory, None);
+        assert_eq!(config.scaffold.template, "default");
+        assert_eq!(config.scaffold.answer_type, "u64");
+        assert_eq!(config.readme.marker, "<!-- timings -->");
+    }
+
+    #[test]
+    fn defaults_missing_keys() {
+        let config = Config::parse("[bench]\nbudget = 250\n").unwrap();
+        assert_eq!(
+            config.bench.limit,
+            BenchLimit::Budget(Duration::from_millis(250))
+        );
+        assert_eq!(
+            Config {
+                bench: Config::default().bench,
+                ..config
+            },
+            Config::default()
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_configs() {
+        for config in [
+            "year = \"2017\"",
+            "year = 100000",
+            "yaer = 2017",
+            "paths = \"data\"",
+            "[paths]\ninput = \"x\"",
+            "[bench]\nsamples = 1\nbudget = 1",
+            "[bench]\nwarmup = -1",
+            "[limits]\ntimeout = -1",
+            "[limits]\nmemory = \"1G\"",
+            "[readme]\nmarker = 1",
+        ] {
+            assert!(Config::parse(config).is_err(), "{config}");
+        }
+    }
+}
@@ -0,0 +1,390 @@
+/// Live progress of multi-day runs, shown with `--dashboard`.
+///
+/// On a terminal, every day is redrawn in place with its status, elapsed time and answers, below
+/// a header with the running total. When stdout is not a terminal, a plain line is printed for
+/// each day once it finished instead.
+use std::{
+    io::{self, IsTerminal, Write},
+    time::{Duration, Instant},
+};
+
+use crate::template::{
+    report::{PartReport, Status, PARSE_PART},
+    stats::format_nanos,
+    timings::Timing,
+    Puzzle, Registry, ANSI_BOLD, ANSI_RESET,
+};
+
+/// How often the dashboard is redrawn while days are running.
+pub const REFRESH_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Answers longer than this are cut off.
+const MAX_ANSWER_LEN: usize = 24;
+
+const SPINNER: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+
+const ANSI_CLEAR: &str = "\x1b[2J\x1b[H";
+const ANSI_HOME: &str = "\x1b[H";
+const ANSI_CLEAR_LINE: &str = "\x1b[K";
+const ANSI_CLEAR_BELOW: &str = "\x1b[J";
+
+#[derive(Clone, Debug, PartialEq)]
+enum State {
+    NotSolved,
+    Pending,
+    Running {
+        since: Instant,
+    },
+    Finished {
+        /// Why the day failed, `None` if it passed.
+        failure: Option<String>,
+        total_nanos: f64,
+        /// The result of every part, e.g. `1: 42`.
+        parts: Vec<String>,
+    },
+}
+
+pub struct Dashboard {
+    days: Vec<(Puzzle, State)>,
+    with_year: bool,
+    started: Instant,
+    /// Whether stdout is a terminal that is redrawn, otherwise plain lines are printed.
+    interactive: bool,
+    drawn: bool,
+}
+
+impl Dashboard {
+    pub fn new(puzzles: &[Puzzle], registry: &Registry, with_year: bool) -> Self {
+        let days = puzzles
+            .iter()
+            .map(|&puzzle| {
+                let state = if registry.get(puzzle).is_some() {
+                    State::Pending
+                } else {
+                    State::NotSolved
+                };
+                (puzzle, state)
+            })
+            .collect();
+
+        Self {
+            days,
+            with_year,
+            started: Instant::now(),
+            interactive: io::stdout().is_terminal(),
+            drawn: false,
+        }
+    }
+
+    pub fn start(&mut self, puzzle: Puzzle) {
+        if let Some(state) = self.state_mut(puzzle) {
+            *state = State::Running {
+                since: Instant::now(),
+            };
+        }
+    }
+
+    /// Mark a day as finished. `failure` is the reason it failed, `None` if it passed.
+    pub fn finish(&mut self, puzzle: Puzzle, failure: Option<String>, reports: &[PartReport]) {
+        let Some(state) = self.state_mut(puzzle) else {
+            return;
+        };
+
+        *state = State::Finished {
+            failure,
+            total_nanos: Timing::from_reports(puzzle.day, reports).total_nanos,
+            parts: reports.iter().filter_map(describe_part).collect(),
+        };
+
+        if !self.interactive {
+            let now = Instant::now();
+            let line = self
+                .days
+                .iter()
+                .find(|(p, _)| *p == puzzle)
+                .map(|(puzzle, state)| self.day_line(*puzzle, state, now));
+            if let Some(line) = line {
+                println!("{line}");
+            }
+        }
+    }
+
+    /// Redraw the dashboard, if stdout is a terminal.
+    pub fn draw(&mut self) {
+        if !self.interactive {
+            return;
+        }
+
+        let mut frame = String::from(if self.drawn { ANSI_HOME } else { ANSI_CLEAR });
+        for line in self.lines(Instant::now()) {
+            frame += &line;
+            frame += ANSI_CLEAR_LINE;
+            frame.push('\n');
+        }
+        frame += ANSI_CLEAR_BELOW;
+
+        let mut stdout = io::stdout();
+        let _ = stdout.write_all(frame.as_bytes());
+        let _ = stdout.flush();
+        self.drawn = true;
+    }
+
+    /// Draw the final state, or print the total if stdout is not a terminal.
+    pub fn close(&mut self) {
+        if self.interactive {
+            self.draw();
+        } else {
+            println!("{}", self.total_line());
+        }
+    }
+
+    fn state_mut(&mut self, puzzle: Puzzle) -> Option<&mut State> {
+        self.days
+            .iter_mut()
+            .find(|(p, _)| *p == puzzle)
+            .map(|(_, state)| state)
+    }
+
+    fn lines(&self, now: Instant) -> Vec<String> {
+        let mut lines = vec![self.header_line(now), String::new()];
+        lines.extend(
+            self.days
+                .iter()
+                .map(|(puzzle, state)| self.day_line(*puzzle, state, now)),
+        );
+        lines.push(String::new());
+        lines.push(self.total_line());
+        lines
+    }
+
+    fn header_line(&self, now: Instant) -> String {
+        let count = |f: fn(&State) -> bool| self.days.iter().filter(|(_, s)| f(s)).count();
+
+        let solved = count(|s| *s != State::NotSolved);
+        let finished = count(|s| matches!(s, State::Finished { .. }));
+        let failed = count(|s| {
+            matches!(
+                s,
+                State::Finished {
+                    failure: Some(_),
+                    ..
+                }
+            )
+        });
+        let running = count(|s| matches!(s, State::Running { .. }));
+
+        format!(
+            "{ANSI_BOLD}Running{ANSI_RESET} {finished}/{solved} done, {failed} failed, {running} running ({:.1?})",
+            now.duration_since(self.started)
+        )
+    }
+
+    fn day_line(&self, puzzle: Puzzle, state: &State, now: Instant) -> String {
+        let label = puzzle.label(self.with_year);
+
+        let (symbol, status, time, details) = match state {
+            State::NotSolved => ('-', "unsolved", String::new(), String::new()),
+            State::Pending => ('·', "pending", String::new(), String::new()),
+            State::Running { since } => {
+                let elapsed = now.duration_since(*since);
+                let frame = (elapsed.as_millis() / REFRESH_INTERVAL.as_millis()) as usize;
+                (
+                    SPINNER[frame % SPINNER.len()],
+                    "running",
+                    format!("{elapsed:.1?}"),
+                    String::new(),
+                )
+            }
+            State::Finished {
+                failure,
+                total_nanos,
+                parts,
+            } => {
+                let mut details = parts.join("  ");
+                if let Some(failure) = failure {
+                    details = format!("{failure}  {details}");
+                }
+                let (symbol, status) = if failure.is_some() {
+                    ('✖', "failed")
+                } else {
+                    ('✔', "done")
+                };
+                // a day that failed before reporting anything has no time.
+                let time = if parts.is_empty() {
+                    String::new()
+                } else {
+                    format_nanos(*total_nanos)
+                };
+                (symbol, status, time, details.trim_end().to_string())
+            }
+        };
+
+        format!("{label}  {symbol} {status:<8} {time:>9}  {details}")
+            .trim_end()
+            .to_string()
+    }
+
+    fn total_line(&self) -> String {
+        let total_nanos: f64 = self
+            .days
+            .iter()
+            .filter_map(|(_, state)| match state {
+                State::Finished { total_nanos, .. } => Some(total_nanos),
+                _ => None,
+            })
+            .sum();
+
+        format!(
+            "{ANSI_BOLD}Total:{ANSI_RESET} {:.2}ms",
+            total_nanos / 1_000_000_f64
+        )
+    }
+}
+
+/// Describe the result of a part for the dashboard, parse steps only if they failed.
+fn describe_part(report: &PartReport) -> Option<String> {
+    let name = if report.part == PARSE_PART {
+        "parse".to_string()
+    } else {
+        report.part.to_string()
+    };
+
+    let result = match (report.status, &report.answer) {
+        (Status::Solved, _) if report.part == PARSE_PART => return None,
+        (Status::Solved, Some(answer)) if answer.contains('\n') => "▼".to_string(),
+        (Status::Solved, Some(answer)) if answer.chars().count() > MAX_ANSWER_LEN => {
+            let cut: String = answer.chars().take(MAX_ANSWER_LEN - 1).collect();
+            format!("{cut}…")
+        }
+        (Status::Solved, Some(answer)) => answer.clone(),
+        (Status::Solved | Status::Unsolved, _) => "✖".to_string(),
+        (Status::Failed, _) => "✖ failed".to_string(),
+    };
+
+    Some(format!("{name}: {result}"))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::{describe_part, Dashboard, State};
+    use crate::{
+        day,
+        template::{
+            report::{PartReport, Status},
+            Puzzle,
+        },
+    };
+
+    fn report(part: u8, answer: Option<&str>, status: Status) -> PartReport {
+        PartReport {
+            duration_nanos: 2e6,
+            ..PartReport::test(day!(1), part, answer, status)
+        }
+    }
+
+    fn dashboard(now: Instant) -> Dashboard {
+        let puzzle = |day| Puzzle::new(2017, day);
+        Dashboard {
+            days: vec![
+                (puzzle(day!(1)), State::Pending),
+                (
+                    puzzle(day!(2)),
+                    State::Running {
+                        since: now - Duration::from_millis(1500),
+                    },
+                ),
+                (
+                    puzzle(day!(3)),
+                    State::Finished {
+                        failure: None,
+                        total_nanos: 3e6,
+                        parts: vec!["1: 42".into(), "2: 7".into()],
+                    },
+                ),
+                (
+                    puzzle(day!(4)),
+                    State::Finished {
+                        failure: Some("timed out".into()),
+                        total_nanos: 1e6,
+                        parts: vec!["1: 3".into()],
+                    },
+                ),
+                (puzzle(day!(5)), State::NotSolved),
+            ],
+            with_year: false,
+            started: now - Duration::from_secs(2),
+            interactive: false,
+            drawn: false,
+        }
+    }
+
+    #[test]
+    fn describes_parts() {
+        assert_eq!(
+            describe_part(&report(1, Some("42"), Status::Solved)),
+            Some("1: 42".into())
+        );
+        assert_eq!(
+            describe_part(&report(2, Some("a\nb"), Status::Solved)),
+            Some("2: ▼".into())
+        );
+        assert_eq!(
+            describe_part(&report(1, Some(&"x".repeat(30)), Status::Solved)),
+            Some(format!("1: {}…", "x".repeat(23)))
+        );
+        assert_eq!(
+            describe_part(&report(2, None, Status::Unsolved)),
+            Some("2: ✖".into())
+        );
+        assert_eq!(describe_part(&report(0, None, Status::Solved)), None);
+        assert_eq!(
+            describe_part(&report(0, None, Status::Failed)),
+            Some("parse: ✖ failed".into())
+        );
+    }
+
+    #[test]
+    fn draws_days() {
+        let now = Instant::now();
+        let lines = dashboard(now).lines(now);
+
+        assert!(lines[0].contains("2/4 done, 1 failed, 1 running (2.0s)"));
+        assert_eq!(lines[2], "Day 01  · pending");
+        assert_eq!(lines[3], "Day 02  ⠴ running       1.5s");
+        assert_eq!(lines[4], "Day 03  ✔ done         3.0ms  1: 42  2: 7");
+        assert_eq!(lines[5], "Day 04  ✖ failed       1.0ms  timed out  1: 3");
+        assert_eq!(lines[6], "Day 05  - unsolved");
+        assert!(lines[8].ends_with("4.00ms"));
+    }
+
+    #[test]
+    fn tracks_days() {
+        let now = Instant::now();
+        let mut dashboard = dashboard(now);
+        let puzzle = Puzzle::new(2017, day!(1));
+
+        dashboard.start(puzzle);
+        assert!(matches!(dashboard.days[0].1, State::Running { .. }));
+
+        dashboard.finish(
+            puzzle,
+            None,
+            &[
+                report(0, None, Status::Solved),
+                report(1, Some("42"), Status::Solved),
+            ],
+        );
+        assert_eq!(
+            dashboard.days[0].1,
+            State::Finished {
+                failure: None,
+                total_nanos: 4e6,
+                parts: vec!["1: 42".into()],
+            }
+        );
+    }
+}
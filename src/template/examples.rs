@@ -0,0 +1,325 @@
+/// Example manifests, stored in `data/examples/NN.toml`.
+///
+/// A manifest lists the example files of a day, the part they apply to and the expected answer.
+/// `solution!` turns it into an `example_manifest` test of the day's binary.
+use std::{fs, io, path::PathBuf};
+use toml::{Table, Value};
+
+use crate::template::params::Param;
+use crate::template::runner::Outcome;
+use crate::template::{Day, Puzzle};
+
+/// A single example of a manifest.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Example {
+    /// File name in `data/examples`, e.g. `01-2.txt`.
+    pub file: String,
+    pub part: u8,
+    /// The expected answer, as printed by the runner.
+    pub answer: String,
+    /// Parameters that override the defaults of the solution for this example.
+    pub params: Vec<Param>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Manifest {
+    pub examples: Vec<Example>,
+}
+
+impl Manifest {
+    pub fn path(puzzle: Puzzle) -> PathBuf {
+        puzzle.paths().manifest(puzzle.day)
+    }
+
+    /// Read the manifest of `puzzle`. If not present, returns an empty manifest.
+    pub fn read(puzzle: Puzzle) -> Result<Self, String> {
+        match fs::read_to_string(Self::path(puzzle)) {
+            Ok(s) => Self::parse(&s, puzzle.day),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    pub fn parse(s: &str, day: Day) -> Result<Self, String> {
+        let table: Table = s
+            .parse()
+            .map_err(|e: toml::de::Error| format!("not a valid TOML file: {}", e.message()))?;
+
+        let examples = match table.get("example") {
+            None => vec![],
+            Some(Value::Array(entries)) => entries
+                .iter()
+                .enumerate()
+                .map(|(i, entry)| {
+                    Example::parse(entry, day).map_err(|e| format!("example {}: {e}", i + 1))
+                })
+                .collect::<Result<_, _>>()?,
+            Some(_) => return Err("expected `example` to be an array of tables.".into()),
+        };
+
+        Ok(Self { examples })
+    }
+
+    /// Append `examples` to the manifest of `puzzle`, creating it if needed.
+    /// Unlike storing the whole manifest, this keeps existing entries and comments as they are.
+    pub fn append(puzzle: Puzzle, examples: &[Example]) -> Result<(), io::Error> {
+        let path = Self::path(puzzle);
+        let mut contents = match fs::read_to_string(&path) {
+            Ok(s) => s,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e),
+        };
+
+        for example in examples {
+            if !contents.is_empty() && !contents.ends_with("\n\n") {
+                contents.push('\n');
+            }
+            contents.push_str(&example.to_toml());
+        }
+
+        fs::write(path, contents)
+    }
+
+    /// Whether the manifest has an entry for `part` of the example in `file`.
+    pub fn contains(&self, file: &str, part: u8) -> bool {
+        self.examples
+            .iter()
+            .any(|example| example.file == file && example.part == part)
+    }
+}
+
+impl Example {
+    fn parse(value: &Value, day: Day) -> Result<Self, String> {
+        let table = value.as_table().ok_or("expected example to be a table.")?;
+
+        if let Some(key) = table
+            .keys()
+            .find(|k| !["file", "part", "answer", "params"].contains(&k.as_str()))
+        {
+            return Err(format!("unknown key `{key}`."));
+        }
+
+        let file = match table.get("file") {
+            None => format!("{day}.txt"),
+            Some(Value::String(file)) => file.clone(),
+            Some(_) => return Err("expected `file` to be a string.".into()),
+        };
+
+        let part = match table.get("part") {
+            Some(Value::Integer(part @ 1..=2)) => *part as u8,
+            _ => return Err("expected `part` to be 1 or 2.".into()),
+        };
+
+        let answer = match table.get("answer") {
+            Some(Value::String(answer)) => answer.clone(),
+            Some(Value::Integer(answer)) => answer.to_string(),
+            _ => return Err("expected `answer` to be a string or an integer.".into()),
+        };
+
+        let params = match table.get("params") {
+            None => vec![],
+            Some(Value::Table(params)) => params
+                .iter()
+                .map(|(key, value)| {
+                    let value = match value {
+                        Value::String(s) => s.clone(),
+                        Value::Integer(i) => i.to_string(),
+                        Value::Float(f) => f.to_string(),
+                        Value::Boolean(b) => b.to_string(),
+                        _ => return Err(format!("expected `params.{key}` to be a scalar.")),
+                    };
+                    Ok(Param {
+                        key: key.clone(),
+                        value,
+                    })
+                })
+                .collect::<Result<_, String>>()?,
+            Some(_) => return Err("expected `params` to be a table.".into()),
+        };
+
+        Ok(Self {
+            file,
+            part,
+            answer,
+            params,
+        })
+    }
+
+    fn to_toml(&self) -> String {
+        // Numeric answers are written as integers, like in hand-written manifests.
+        let answer = match self.answer.parse::<i64>() {
+            Ok(n) if n.to_string() == self.answer => self.answer.clone(),
+            _ => toml_string(&self.answer),
+        };
+
+        let mut toml = format!(
+            "[[example]]\nfile = {}\npart = {}\nanswer = {answer}\n",
+            toml_string(&self.file),
+            self.part
+        );
+
+        if !self.params.is_empty() {
+            let params: Vec<String> = self
+                .params
+                .iter()
+                .map(|p| format!("{} = {}", p.key, toml_string(&p.value)))
+                .collect();
+            toml.push_str(&format!("params = {{ {} }}\n", params.join(", ")));
+        }
+
+        toml
+    }
+}
+
+fn toml_string(s: &str) -> String {
+    let escaped = s
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n");
+    format!("\"{escaped}\"")
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Signature of the function that runs a part of a solution against an example.
+type Solve = fn(String, u8, &[Param]) -> Outcome;
+
+/// Run every example in the manifest of `puzzle` through `solve` and compare the answers.
+/// Panics with a list of all failed examples, this is the body of the test generated by
+/// `solution!`.
+#[doc(hidden)]
+pub fn check(puzzle: Puzzle, parts: &[u8], solve: Solve) {
+    let manifest = Manifest::read(puzzle).unwrap_or_else(|e| {
+        panic!("failed to read `{}`: {e}", Manifest::path(puzzle).display());
+    });
+
+    let mut failures: Vec<String> = vec![];
+
+    for example in &manifest.examples {
+        let name = format!("{} (part {})", example.file, example.part);
+
+        match check_example(puzzle, parts, example, solve) {
+            Ok(()) => println!("✔ {name}"),
+            Err(e) => {
+                println!("✖ {name}: {e}");
+                failures.push(format!("{name}: {e}"));
+            }
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "{} of {} examples failed:\n{}",
+        failures.len(),
+        manifest.examples.len(),
+        failures.join("\n")
+    );
+}
+
+fn check_example(
+    puzzle: Puzzle,
+    parts: &[u8],
+    example: &Example,
+    solve: Solve,
+) -> Result<(), String> {
+    if !parts.contains(&example.part) {
+        return Err(format!("day {} has no part {}", puzzle.day, example.part));
+    }
+
+    let path = puzzle.paths().examples.join(&example.file);
+    let input = fs::read_to_string(&path)
+        .map_err(|e| format!("could not open `{}`: {e}", path.display()))?;
+
+    match solve(input, example.part, &example.params) {
+        Outcome::Solved(answer) if answer == example.answer => Ok(()),
+        Outcome::Solved(answer) => Err(format!("expected `{}`, got `{answer}`", example.answer)),
+        Outcome::Unsolved => Err(format!("expected `{}`, got no answer", example.answer)),
+        Outcome::Failed(e) => Err(format!("failed: {e:#}")),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Example, Manifest};
+    use crate::day;
+
+    #[test]
+    fn parses_manifests() {
+        let manifest = Manifest::parse(
+            r#"
+            [[example]]
+            file = "01-1.txt"
+            part = 1
+            answer = 3
+
+            [[example]]
+            part = 2
+            answer = "abc"
+            params = { iterations = 2, name = "x" }
+            "#,
+            day!(1),
+        )
+        .unwrap();
+
+        assert_eq!(
+            manifest.examples,
+            vec![
+                Example {
+                    file: "01-1.txt".into(),
+                    part: 1,
+                    answer: "3".into(),
+                    params: vec![],
+                },
+                Example {
+                    file: "01.txt".into(),
+                    part: 2,
+                    answer: "abc".into(),
+                    params: vec!["iterations=2".parse().unwrap(), "name=x".parse().unwrap()],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn round_trips_examples() {
+        let examples = vec![
+            Example {
+                file: "01-1.txt".into(),
+                part: 1,
+                answer: "3".into(),
+                params: vec![],
+            },
+            Example {
+                file: "01-2.txt".into(),
+                part: 2,
+                answer: "0a\"b".into(),
+                params: vec!["iterations=2".parse().unwrap()],
+            },
+        ];
+
+        let toml: String = examples.iter().map(Example::to_toml).collect();
+        assert!(toml.contains("answer = 3\n"));
+        assert_eq!(Manifest::parse(&toml, day!(1)).unwrap().examples, examples);
+    }
+
+    #[test]
+    fn handles_empty_manifests() {
+        let manifest = Manifest::parse("# no examples yet\n", day!(1)).unwrap();
+        assert_eq!(manifest.examples.len(), 0);
+    }
+
+    #[test]
+    fn rejects_malformed_examples() {
+        for manifest in [
+            "example = 1",
+            "[[example]]\npart = 3\nanswer = 1",
+            "[[example]]\npart = 1",
+            "[[example]]\npart = 1\nanswer = 1\nanwser = 2",
+            "[[example]]\npart = 1\nanswer = 1\nparams = { a = [1] }",
+        ] {
+            assert!(Manifest::parse(manifest, day!(1)).is_err(), "{manifest}");
+        }
+    }
+}
@@ -0,0 +1,229 @@
+//! Extraction of examples from puzzle descriptions.
+//!
+//! Puzzles show an example input in a code block, followed by a walkthrough that ends with the
+//! highlighted answer (`` *`42`* `` in the markdown written by [`super::html`]). Short examples
+//! are listed inline instead, e.g. `` - `1122` produces *`3`*. ``
+//! The results are heuristics and meant to be confirmed by the user.
+use crate::template::markdown::select_part;
+
+/// An example input and its expected answer, as found in the description of `part`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Candidate {
+    pub part: u8,
+    pub input: String,
+    pub answer: String,
+}
+
+/// Find the examples of both parts in the markdown of a puzzle.
+pub fn extract(markdown: &str) -> Vec<Candidate> {
+    let part_one = select_part(markdown, Some(1)).unwrap_or(markdown);
+    let mut candidates = extract_part(part_one, 1);
+
+    if let Some(part_two) = select_part(markdown, Some(2)) {
+        let found = extract_part(part_two, 2);
+
+        if found.is_empty() {
+            // Part two usually refers back to the example of part one.
+            let reused = candidates.first().map(|c| c.input.clone());
+            let answer = part_two.lines().flat_map(highlights).last();
+
+            if let (Some(input), Some(answer)) = (reused, answer) {
+                candidates.push(Candidate {
+                    part: 2,
+                    input,
+                    answer: answer.into(),
+                });
+            }
+        } else {
+            candidates.extend(found);
+        }
+    }
+
+    candidates
+}
+
+fn extract_part(markdown: &str, part: u8) -> Vec<Candidate> {
+    let mut candidates = vec![];
+    // Code blocks that have not been followed by an answer yet. Blocks after the first one are
+    // usually part of the walkthrough, so the answer belongs to the first.
+    let mut pending: Vec<String> = vec![];
+    let mut answer: Option<&str> = None;
+
+    let mut lines = markdown.lines();
+    while let Some(line) = lines.next() {
+        if line.starts_with("```") {
+            let block: Vec<&str> = lines
+                .by_ref()
+                .take_while(|l| !l.starts_with("```"))
+                .collect();
+
+            if answer.is_some() {
+                candidates.extend(finish(part, &mut pending, answer.take()));
+            }
+            if !block.is_empty() {
+                pending.push(block.join("\n"));
+            }
+        } else if let Some((input, answer)) = inline_example(line) {
+            candidates.push(Candidate {
+                part,
+                input: input.into(),
+                answer: answer.into(),
+            });
+        } else if !pending.is_empty() {
+            if let Some(highlight) = highlights(line).pop() {
+                answer = Some(highlight);
+            }
+        }
+    }
+
+    candidates.extend(finish(part, &mut pending, answer));
+    candidates
+}
+
+/// Pair the first pending code block with `answer` and start over.
+fn finish(part: u8, pending: &mut Vec<String>, answer: Option<&str>) -> Option<Candidate> {
+    let candidate = match (pending.first(), answer) {
+        (Some(input), Some(answer)) => Some(Candidate {
+            part,
+            input: input.clone(),
+            answer: answer.into(),
+        }),
+        _ => None,
+    };
+    pending.clear();
+    candidate
+}
+
+/// A list item that starts with an example input and contains its answer, e.g.
+/// `` - `1122` produces a sum of *`3`*. ``
+fn inline_example(line: &str) -> Option<(&str, &str)> {
+    let item = line.trim_start().strip_prefix("- `")?;
+    let (input, rest) = item.split_once('`')?;
+    let answer = highlights(rest).pop()?;
+
+    (!input.trim().is_empty()).then_some((input, answer))
+}
+
+/// The contents of all highlighted code spans in `line`.
+fn highlights(line: &str) -> Vec<&str> {
+    let mut found = vec![];
+    let mut rest = line;
+
+    while let Some(start) = rest.find("*`") {
+        rest = &rest[start + 2..];
+        let Some(end) = rest.find("`*") else {
+            break;
+        };
+        found.push(&rest[..end]);
+        rest = &rest[end + 2..];
+    }
+
+    found
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{extract, Candidate};
+
+    fn candidate(part: u8, input: &str, answer: &str) -> Candidate {
+        Candidate {
+            part,
+            input: input.into(),
+            answer: answer.into(),
+        }
+    }
+
+    #[test]
+    fn extracts_code_blocks() {
+        let markdown = [
+            "## --- Day 8: Registers ---",
+            "",
+            "For example:",
+            "",
+            "```",
+            "b inc 5",
+            "a inc 1",
+            "```",
+            "",
+            "These instructions would be processed as follows:",
+            "",
+            "```",
+            "b = 5",
+            "```",
+            "",
+            "The largest value is `1`, so the answer is *`1`*.",
+            "",
+            "## --- Part Two ---",
+            "",
+            "In the above example, the highest value held was *`10`*.",
+        ]
+        .join("\n");
+
+        assert_eq!(
+            extract(&markdown),
+            vec![
+                candidate(1, "b inc 5\na inc 1", "1"),
+                candidate(2, "b inc 5\na inc 1", "10"),
+            ]
+        );
+    }
+
+    #[test]
+    fn extracts_several_examples() {
+        let markdown = [
+            "```",
+            "0 2 7 0",
+            "```",
+            "",
+            "Takes *`5`* steps.",
+            "",
+            "```",
+            "1 2",
+            "```",
+            "",
+            "Takes `3`, then *`4`* steps.",
+            "",
+            "```",
+            "no answer",
+            "```",
+        ]
+        .join("\n");
+
+        assert_eq!(
+            extract(&markdown),
+            vec![candidate(1, "0 2 7 0", "5"), candidate(1, "1 2", "4")]
+        );
+    }
+
+    #[test]
+    fn extracts_inline_examples() {
+        let markdown = [
+            "For example:",
+            "",
+            "- `1122` produces a sum of *`3`*.",
+            "- `91212129` produces *`9`* because the only digit that matches is `9`.",
+            "- The sum is the *answer*.",
+            "",
+            "## --- Part Two ---",
+            "",
+            "- `1212` produces *`6`*.",
+        ]
+        .join("\n");
+
+        assert_eq!(
+            extract(&markdown),
+            vec![
+                candidate(1, "1122", "3"),
+                candidate(1, "91212129", "9"),
+                candidate(2, "1212", "6"),
+            ]
+        );
+    }
+
+    #[test]
+    fn handles_descriptions_without_examples() {
+        assert_eq!(extract("## --- Day 1 ---\n\nNothing *`here`*.\n"), vec![]);
+    }
+}
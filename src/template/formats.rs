@@ -0,0 +1,533 @@
+/// Machine-readable reports of `all`, `time` and `verify`, selected with `--format`.
+///
+/// A report has one row per part that ran, with its answer, status, duration and sample count.
+/// It is printed to stdout once all days finished, while the usual output goes to stderr.
+use std::{collections::HashMap, fmt, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::{
+    answers::{Check, Verdict},
+    report::{Status, PARSE_PART},
+    run_multi::{Output, RunSummary},
+    stats::format_nanos,
+    Puzzle,
+};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Format {
+    /// The usual human-readable output, without a report.
+    #[default]
+    Text,
+    Json,
+    Csv,
+    Junit,
+    Markdown,
+}
+
+impl Format {
+    /// Where the human-readable output goes, stdout is kept free for the report.
+    pub fn output(self) -> Output {
+        match self {
+            Self::Text => Output::Stdout,
+            _ => Output::Stderr,
+        }
+    }
+
+    /// Render the report of `rows`, empty for [`Format::Text`].
+    pub fn render(self, rows: &[Row]) -> String {
+        match self {
+            Self::Text => String::new(),
+            Self::Json => render_json(rows),
+            Self::Csv => render_csv(rows),
+            Self::Junit => render_junit(rows),
+            Self::Markdown => render_markdown(rows),
+        }
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            "csv" => Ok(Self::Csv),
+            "junit" => Ok(Self::Junit),
+            "markdown" => Ok(Self::Markdown),
+            _ => Err(format!(
+                "expecting one of `text`, `json`, `csv`, `junit` or `markdown`, got `{s}`"
+            )),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Status of a row: the status of the part, or its verdict when verifying.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RowStatus {
+    Solved,
+    Unsolved,
+    Failed,
+    Correct,
+    Mismatch,
+    /// Solved, but there is no known answer to verify.
+    Missing,
+}
+
+impl RowStatus {
+    pub fn is_failure(self) -> bool {
+        matches!(self, Self::Failed | Self::Mismatch)
+    }
+}
+
+impl fmt::Display for RowStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Solved => "solved",
+            Self::Unsolved => "unsolved",
+            Self::Failed => "failed",
+            Self::Correct => "correct",
+            Self::Mismatch => "mismatch",
+            Self::Missing => "missing",
+        })
+    }
+}
+
+impl From<Status> for RowStatus {
+    fn from(value: Status) -> Self {
+        match value {
+            Status::Solved => Self::Solved,
+            Status::Unsolved => Self::Unsolved,
+            Status::Failed => Self::Failed,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Row {
+    pub puzzle: Puzzle,
+    /// The part number, [`PARSE_PART`] for the parse step, or `None` for a day that was stopped
+    /// before it reported the failing part.
+    pub part: Option<u8>,
+    pub answer: Option<String>,
+    pub status: RowStatus,
+    pub duration_nanos: f64,
+    pub samples: u128,
+    /// Why the part failed, or what was expected of it.
+    pub message: Option<String>,
+}
+
+/// Rows of all parts that ran, and of the days that were aborted, in order of year and day.
+pub fn rows(summary: &RunSummary) -> Vec<Row> {
+    let mut rows: Vec<Row> = summary
+        .reports
+        .iter()
+        .flat_map(|(&year, reports)| {
+            reports.iter().map(move |report| Row {
+                puzzle: Puzzle::new(year, report.day),
+                part: Some(report.part),
+                answer: report.answer.clone(),
+                status: report.status.into(),
+                duration_nanos: report.duration_nanos,
+                samples: report.samples,
+                message: report.error.clone(),
+            })
+        })
+        .collect();
+
+    rows.extend(summary.aborted.iter().map(|(puzzle, reason)| Row {
+        puzzle: *puzzle,
+        part: None,
+        answer: None,
+        status: RowStatus::Failed,
+        duration_nanos: 0.0,
+        samples: 0,
+        message: Some(reason.clone()),
+    }));
+
+    sort(&mut rows);
+    rows
+}
+
+/// Replace the status of the checked parts with their verdict. Parts that were expected to have
+/// an answer but did not run are added.
+pub fn apply_checks(rows: &mut Vec<Row>, checks: &[(Puzzle, Check)]) {
+    for (puzzle, check) in checks {
+        let (status, message) = match &check.verdict {
+            Verdict::Correct => (RowStatus::Correct, None),
+            Verdict::Mismatch { expected, .. } => {
+                (RowStatus::Mismatch, Some(format!("expected `{expected}`")))
+            }
+            Verdict::Missing { .. } => (RowStatus::Missing, Some("no known answer".into())),
+            Verdict::Failed { error } => (RowStatus::Failed, error.clone()),
+        };
+
+        let row = rows
+            .iter_mut()
+            .find(|r| r.puzzle == *puzzle && r.part == Some(check.part));
+
+        match row {
+            Some(row) => {
+                row.status = status;
+                row.message = message;
+            }
+            None => rows.push(Row {
+                puzzle: *puzzle,
+                part: Some(check.part),
+                answer: None,
+                status,
+                duration_nanos: 0.0,
+                samples: 0,
+                message,
+            }),
+        }
+    }
+
+    sort(rows);
+}
+
+/// Sort by puzzle and part, the row of an aborted day comes after its parts.
+fn sort(rows: &mut [Row]) {
+    rows.sort_by_key(|r| (r.puzzle, r.part.is_none(), r.part));
+}
+
+/// Name of a part in the reports: `parse`, `run` for an aborted day, or its number.
+fn part_name(part: Option<u8>) -> String {
+    match part {
+        None => "run".into(),
+        Some(PARSE_PART) => "parse".into(),
+        Some(part) => part.to_string(),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn render_json(rows: &[Row]) -> String {
+    let json = JsonValue::Array(rows.iter().map(JsonValue::from).collect());
+    let mut s = json.stringify().unwrap();
+    s.push('\n');
+    s
+}
+
+impl From<&Row> for JsonValue {
+    fn from(value: &Row) -> Self {
+        let optional = |s: &Option<String>| match s {
+            Some(s) => JsonValue::String(s.clone()),
+            None => JsonValue::Null,
+        };
+
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("year".into(), JsonValue::Number(value.puzzle.year.into()));
+        map.insert(
+            "day".into(),
+            JsonValue::Number(value.puzzle.day.into_inner().into()),
+        );
+        map.insert(
+            "part".into(),
+            match value.part {
+                Some(part) => JsonValue::Number(part.into()),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert("answer".into(), optional(&value.answer));
+        map.insert("status".into(), JsonValue::String(value.status.to_string()));
+        map.insert(
+            "duration_nanos".into(),
+            JsonValue::Number(value.duration_nanos),
+        );
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert("message".into(), optional(&value.message));
+
+        JsonValue::Object(map)
+    }
+}
+
+fn render_csv(rows: &[Row]) -> String {
+    let mut lines = vec!["year,day,part,answer,status,duration_nanos,samples,message".to_string()];
+
+    lines.extend(rows.iter().map(|row| {
+        [
+            row.puzzle.year.to_string(),
+            row.puzzle.day.into_inner().to_string(),
+            row.part.map_or(String::new(), |_| part_name(row.part)),
+            row.answer.as_deref().map_or(String::new(), csv_field),
+            row.status.to_string(),
+            format!("{:.0}", row.duration_nanos),
+            row.samples.to_string(),
+            row.message.as_deref().map_or(String::new(), csv_field),
+        ]
+        .join(",")
+    }));
+
+    lines.join("\n") + "\n"
+}
+
+/// Quote a field if it contains a separator, quote or line break.
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.into()
+    }
+}
+
+fn render_junit(rows: &[Row]) -> String {
+    let failures = rows.iter().filter(|r| r.status.is_failure()).count();
+    let skipped = rows
+        .iter()
+        .filter(|r| r.status == RowStatus::Unsolved)
+        .count();
+    let seconds = |rows: &[Row]| rows.iter().map(|r| r.duration_nanos).sum::<f64>() / 1e9;
+
+    let mut lines = vec![
+        r#"<?xml version="1.0" encoding="UTF-8"?>"#.to_string(),
+        format!(
+            r#"<testsuites name="advent_of_code" tests="{}" failures="{failures}" skipped="{skipped}" time="{:.6}">"#,
+            rows.len(),
+            seconds(rows)
+        ),
+    ];
+
+    // one suite per day, rows are sorted by puzzle.
+    for day_rows in rows.chunk_by(|a, b| a.puzzle == b.puzzle) {
+        let puzzle = day_rows[0].puzzle;
+        lines.push(format!(
+            r#"  <testsuite name="{}" tests="{}" failures="{}" skipped="{}" time="{:.6}">"#,
+            puzzle.label(true),
+            day_rows.len(),
+            day_rows.iter().filter(|r| r.status.is_failure()).count(),
+            day_rows
+                .iter()
+                .filter(|r| r.status == RowStatus::Unsolved)
+                .count(),
+            seconds(day_rows)
+        ));
+
+        for row in day_rows {
+            let name = match row.part {
+                Some(part) if part != PARSE_PART => format!("part {part}"),
+                part => part_name(part),
+            };
+            lines.push(format!(
+                r#"    <testcase classname="advent_of_code.{puzzle}" name="{name}" time="{:.6}">"#,
+                row.duration_nanos / 1e9
+            ));
+
+            if row.status.is_failure() {
+                let message = row
+                    .message
+                    .clone()
+                    .unwrap_or_else(|| row.status.to_string());
+                lines.push(format!(
+                    r#"      <failure message="{}" type="{}">{}</failure>"#,
+                    xml_escape(message.lines().next().unwrap_or_default()),
+                    row.status,
+                    xml_escape(&message)
+                ));
+            } else if row.status == RowStatus::Unsolved {
+                lines.push(r#"      <skipped message="unsolved"/>"#.into());
+            }
+
+            if let Some(answer) = &row.answer {
+                lines.push(format!(
+                    "      <system-out>answer: {}, samples: {}</system-out>",
+                    xml_escape(answer),
+                    row.samples
+                ));
+            }
+            lines.push("    </testcase>".into());
+        }
+
+        lines.push("  </testsuite>".into());
+    }
+
+    lines.push("</testsuites>".into());
+    lines.join("\n") + "\n"
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+fn render_markdown(rows: &[Row]) -> String {
+    let with_year = rows
+        .windows(2)
+        .any(|w| w[0].puzzle.year != w[1].puzzle.year);
+
+    let mut lines = vec![
+        "| Day | Part | Answer | Status | Duration | Samples |".to_string(),
+        "| :---: | :---: | :---: | :---: | :---: | :---: |".to_string(),
+    ];
+
+    lines.extend(rows.iter().map(|row| {
+        let status = match &row.message {
+            Some(message) => format!(
+                "{}: {}",
+                row.status,
+                message.lines().next().unwrap_or_default()
+            ),
+            None => row.status.to_string(),
+        };
+
+        format!(
+            "| {} | {} | {} | {} | `{}` | {} |",
+            row.puzzle.label(with_year),
+            part_name(row.part),
+            row.answer
+                .as_ref()
+                .map_or("-".into(), |answer| format!("`{answer}`")),
+            status.replace('|', "\\|"),
+            format_nanos(row.duration_nanos),
+            row.samples
+        )
+    }));
+
+    lines.join("\n") + "\n"
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::str::FromStr;
+    use tinyjson::JsonValue;
+
+    use super::{apply_checks, Format, Row, RowStatus};
+    use crate::{
+        day,
+        template::{
+            answers::{Check, Verdict},
+            Puzzle,
+        },
+    };
+
+    fn rows() -> Vec<Row> {
+        vec![
+            Row {
+                puzzle: Puzzle::new(2017, day!(1)),
+                part: Some(1),
+                answer: Some("1,2".into()),
+                status: RowStatus::Solved,
+                duration_nanos: 1500.0,
+                samples: 10,
+                message: None,
+            },
+            Row {
+                puzzle: Puzzle::new(2017, day!(1)),
+                part: Some(2),
+                answer: None,
+                status: RowStatus::Failed,
+                duration_nanos: 1e6,
+                samples: 1,
+                message: Some("bad <input>".into()),
+            },
+            Row {
+                puzzle: Puzzle::new(2017, day!(3)),
+                part: None,
+                answer: None,
+                status: RowStatus::Failed,
+                duration_nanos: 0.0,
+                samples: 0,
+                message: Some("timed out".into()),
+            },
+        ]
+    }
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!("junit".parse(), Ok(Format::Junit));
+        assert_eq!("text".parse(), Ok(Format::Text));
+        assert!("xml".parse::<Format>().is_err());
+        assert_eq!(Format::Text.render(&rows()), "");
+    }
+
+    #[test]
+    fn renders_csv() {
+        assert_eq!(
+            Format::Csv.render(&rows()),
+            [
+                "year,day,part,answer,status,duration_nanos,samples,message",
+                "2017,1,1,\"1,2\",solved,1500,10,",
+                "2017,1,2,,failed,1000000,1,bad <input>",
+                "2017,3,,,failed,0,0,timed out",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn renders_json() {
+        let json = JsonValue::from_str(&Format::Json.render(&rows())).unwrap();
+        let rows: &Vec<JsonValue> = json.get().unwrap();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[0]["answer"], JsonValue::String("1,2".into()));
+        assert_eq!(rows[0]["samples"], JsonValue::Number(10.0));
+        assert_eq!(rows[2]["part"], JsonValue::Null);
+    }
+
+    #[test]
+    fn renders_junit() {
+        let xml = Format::Junit.render(&rows());
+        assert!(xml.contains(r#"<testsuites name="advent_of_code" tests="3" failures="2""#));
+        assert!(xml.contains(r#"<testsuite name="2017 Day 01" tests="2" failures="1""#));
+        assert!(xml.contains(r#"name="part 1" time="0.000002">"#));
+        assert!(xml.contains("<system-out>answer: 1,2, samples: 10</system-out>"));
+        assert!(xml.contains(
+            r#"<failure message="bad &lt;input&gt;" type="failed">bad &lt;input&gt;</failure>"#
+        ));
+        assert!(xml.contains(r#"<testcase classname="advent_of_code.2017-03" name="run""#));
+    }
+
+    #[test]
+    fn renders_markdown() {
+        let markdown = Format::Markdown.render(&rows());
+        assert!(markdown.contains("| Day 01 | 1 | `1,2` | solved | `1.5µs` | 10 |"));
+        assert!(markdown.contains("| Day 03 | run | - | failed: timed out | `0.0ns` | 0 |"));
+    }
+
+    #[test]
+    fn applies_checks() {
+        let mut rows = rows();
+        let puzzle = Puzzle::new(2017, day!(1));
+        let check = |part, verdict| {
+            (
+                puzzle,
+                Check {
+                    day: day!(1),
+                    part,
+                    verdict,
+                },
+            )
+        };
+
+        apply_checks(
+            &mut rows,
+            &[
+                check(1, Verdict::Correct),
+                check(
+                    3,
+                    Verdict::Mismatch {
+                        expected: "7".into(),
+                        actual: None,
+                    },
+                ),
+            ],
+        );
+
+        assert_eq!(rows[0].status, RowStatus::Correct);
+        assert_eq!(rows[1].status, RowStatus::Failed);
+        assert_eq!(rows[2].part, Some(3));
+        assert_eq!(rows[2].status, RowStatus::Mismatch);
+        assert_eq!(rows[2].message, Some("expected `7`".into()));
+        assert_eq!(rows[3].part, None);
+    }
+}
@@ -0,0 +1,401 @@
+/// History of stored benchmark runs, kept in `data/timings_history.json`.
+///
+/// Every `cargo time --store` appends the timings of the days it ran, together with the time,
+/// git commit and compiler version. `cargo time --compare` uses the history as a baseline to
+/// detect regressions.
+use std::{collections::HashMap, fs, io, process::Command, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::config;
+use crate::template::report::PARSE_PART;
+use crate::template::stats::BenchStats;
+use crate::template::timings::{Timing, Timings};
+use crate::template::{unix_now, Day};
+
+/// Name of the history file in the data directory.
+static HISTORY_FILE_NAME: &str = "timings_history.json";
+
+/// A single stored benchmark run.
+#[derive(Clone, Debug)]
+pub struct HistoryEntry {
+    /// Time of the run, in seconds since the unix epoch.
+    pub timestamp: u64,
+    /// The git commit that was checked out, if the repository is a git repository.
+    pub commit: Option<String>,
+    /// Output of `rustc --version` for the compiler that built the solutions.
+    pub rustc: Option<String>,
+    pub timings: Timings,
+}
+
+impl HistoryEntry {
+    /// Create an entry for a run that just finished.
+    pub fn now(timings: Timings) -> Self {
+        Self {
+            timestamp: unix_now(),
+            commit: git_rev_parse("HEAD"),
+            rustc: option_env!("AOC_RUSTC_VERSION")
+                .filter(|v| !v.is_empty())
+                .map(Into::into),
+            timings,
+        }
+    }
+}
+
+/// Selects the history entries to compare a run with.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Baseline {
+    /// The most recent stored timing of each day.
+    Latest,
+    /// The most recent stored timing of each day at the given commit hash.
+    Commit(String),
+}
+
+impl Baseline {
+    /// Parse `latest` or resolve a git ref, e.g. `HEAD~1` or `main`, to its commit hash.
+    pub fn parse(reference: &str) -> Result<Self, String> {
+        if reference == "latest" {
+            return Ok(Baseline::Latest);
+        }
+
+        git_rev_parse(&format!("{reference}^{{commit}}"))
+            .map(Baseline::Commit)
+            .ok_or(format!("`{reference}` is not a known git ref."))
+    }
+}
+
+impl std::fmt::Display for Baseline {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Baseline::Latest => write!(f, "latest"),
+            Baseline::Commit(hash) => write!(f, "{}", &hash[..hash.len().min(7)]),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct History {
+    pub entries: Vec<HistoryEntry>,
+}
+
+impl History {
+    /// Dehydrate the history of `year` to a JSON file.
+    pub fn store_file(&self, year: u16) -> Result<(), io::Error> {
+        let json = JsonValue::from(self);
+        let mut file = config::get()
+            .paths_for(year)
+            .create_data_file(HISTORY_FILE_NAME)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate the history of `year` from a JSON file. If not present, returns an empty history.
+    pub fn read_from_file(year: u16) -> Result<Self, String> {
+        match fs::read_to_string(config::get().paths_for(year).data_file(HISTORY_FILE_NAME)) {
+            Ok(s) => History::try_from(s),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(History::default()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    /// The most recent timing of each day among the entries selected by `baseline`.
+    pub fn baseline(&self, baseline: &Baseline) -> Timings {
+        self.entries
+            .iter()
+            .filter(|entry| match baseline {
+                Baseline::Latest => true,
+                Baseline::Commit(hash) => entry.commit.as_ref() == Some(hash),
+            })
+            .fold(Timings::default(), |acc, entry| acc.merge(&entry.timings))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Change of a part's median between a baseline and the current run.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Delta {
+    pub day: Day,
+    /// The part number, or [`PARSE_PART`] for the parse step.
+    pub part: u8,
+    pub before: f64,
+    pub after: f64,
+}
+
+impl Delta {
+    /// Relative change of the median in percent, positive if the part got slower.
+    pub fn percent(&self) -> f64 {
+        (self.after - self.before) / self.before * 100.0
+    }
+
+    pub fn is_regression(&self, threshold_percent: f64) -> bool {
+        self.percent() > threshold_percent
+    }
+}
+
+/// Compare the medians of all parts that have timings in both `baseline` and `current`.
+pub fn compare(baseline: &Timings, current: &Timings) -> Vec<Delta> {
+    let mut deltas = vec![];
+
+    for timing in &current.data {
+        let Some(before) = baseline.data.iter().find(|t| t.day == timing.day) else {
+            continue;
+        };
+
+        for (part, before, after) in parts(before)
+            .into_iter()
+            .zip(parts(timing))
+            .filter_map(|((part, before), (_, after))| Some((part, before?.median, after?.median)))
+        {
+            if before > 0.0 {
+                deltas.push(Delta {
+                    day: timing.day,
+                    part,
+                    before,
+                    after,
+                });
+            }
+        }
+    }
+
+    deltas
+}
+
+fn parts(timing: &Timing) -> [(u8, Option<BenchStats>); 3] {
+    [
+        (PARSE_PART, timing.parse),
+        (1, timing.part_1),
+        (2, timing.part_2),
+    ]
+}
+
+fn git_rev_parse(reference: &str) -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--verify", "--quiet", reference])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    String::from_utf8(output.stdout)
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&History> for JsonValue {
+    fn from(value: &History) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "entries".into(),
+            JsonValue::Array(value.entries.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for History {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_entries = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("entries")
+            .ok_or("expected JSON document to have key `entries`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.entries` to be an array.")?;
+
+        Ok(History {
+            entries: json_entries
+                .iter()
+                .map(HistoryEntry::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<&HistoryEntry> for JsonValue {
+    fn from(value: &HistoryEntry) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+
+        for (key, field) in [("commit", &value.commit), ("rustc", &value.rustc)] {
+            map.insert(
+                key.into(),
+                match field {
+                    Some(x) => JsonValue::String(x.clone()),
+                    None => JsonValue::Null,
+                },
+            );
+        }
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.timings.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for HistoryEntry {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected history entry to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u64)
+            .ok_or("Expected entry.timestamp to be a number.")?;
+
+        let string = |key: &str| json.get(key).and_then(|v| v.get::<String>()).cloned();
+
+        let data = json
+            .get("data")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("Expected entry.data to be an array.")?;
+
+        Ok(HistoryEntry {
+            timestamp,
+            commit: string("commit"),
+            rustc: string("rustc"),
+            timings: Timings {
+                data: data
+                    .iter()
+                    .map(Timing::try_from)
+                    .collect::<Result<_, _>>()?,
+            },
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::{compare, Baseline, Delta, History, HistoryEntry};
+    use crate::{
+        day,
+        template::{
+            stats::BenchStats,
+            timings::{Timing, Timings},
+            Day,
+        },
+    };
+
+    fn timing(day: Day, part_1: f64, part_2: Option<f64>) -> Timing {
+        Timing {
+            day,
+            parse: None,
+            part_1: Some(BenchStats::single(part_1)),
+            part_2: part_2.map(BenchStats::single),
+            failed_parts: vec![],
+            exceeded: None,
+            alloc: BTreeMap::new(),
+            total_nanos: part_1 + part_2.unwrap_or_default(),
+        }
+    }
+
+    fn entry(commit: &str, data: Vec<Timing>) -> HistoryEntry {
+        HistoryEntry {
+            timestamp: 100,
+            commit: Some(commit.into()),
+            rustc: None,
+            timings: Timings { data },
+        }
+    }
+
+    fn get_mock_history() -> History {
+        History {
+            entries: vec![
+                entry(
+                    "aaa",
+                    vec![
+                        timing(day!(1), 100.0, Some(200.0)),
+                        timing(day!(2), 100.0, None),
+                    ],
+                ),
+                entry("bbb", vec![timing(day!(1), 150.0, Some(250.0))]),
+            ],
+        }
+    }
+
+    #[test]
+    fn selects_baselines() {
+        let history = get_mock_history();
+
+        let latest = history.baseline(&Baseline::Latest);
+        assert_eq!(latest.data.len(), 2);
+        assert_eq!(latest.data[0].part_1.unwrap().median, 150.0);
+        assert_eq!(latest.data[1].part_1.unwrap().median, 100.0);
+
+        let commit = history.baseline(&Baseline::Commit("aaa".into()));
+        assert_eq!(commit.data[0].part_1.unwrap().median, 100.0);
+
+        let unknown = history.baseline(&Baseline::Commit("ccc".into()));
+        assert_eq!(unknown.data.len(), 0);
+    }
+
+    #[test]
+    fn compares_timings() {
+        let baseline = get_mock_history().baseline(&Baseline::Latest);
+        let current = Timings {
+            data: vec![
+                timing(day!(1), 180.0, Some(250.0)),
+                timing(day!(3), 100.0, None),
+            ],
+        };
+
+        let deltas = compare(&baseline, &current);
+        assert_eq!(
+            deltas,
+            vec![
+                Delta {
+                    day: day!(1),
+                    part: 1,
+                    before: 150.0,
+                    after: 180.0,
+                },
+                Delta {
+                    day: day!(1),
+                    part: 2,
+                    before: 250.0,
+                    after: 250.0,
+                },
+            ]
+        );
+        assert_eq!(deltas[0].percent(), 20.0);
+        assert!(deltas[0].is_regression(10.0));
+        assert!(!deltas[0].is_regression(25.0));
+        assert!(!deltas[1].is_regression(0.0));
+    }
+
+    #[test]
+    fn round_trips_json() {
+        let history = get_mock_history();
+        let json = tinyjson::JsonValue::from(&history).stringify().unwrap();
+        let parsed = History::try_from(json).unwrap();
+        assert_eq!(parsed.entries.len(), 2);
+        assert_eq!(parsed.entries[1].commit.as_deref(), Some("bbb"));
+        assert_eq!(parsed.entries[0].timings.data.len(), 2);
+    }
+}
@@ -0,0 +1,529 @@
+//! Minimal HTML to markdown conversion for puzzle pages.
+//!
+//! Advent of Code puzzle descriptions only use a handful of tags (`h2`, `p`, `pre`, `code`, `em`,
+//! `a`, `ul` / `li` and `span`), so this does not try to be a general purpose HTML parser.
+//! Only the contents of `<article>` elements are converted.
+
+/// Tags that never have children.
+const VOID_TAGS: [&str; 6] = ["br", "hr", "img", "input", "link", "meta"];
+
+/// Tags whose contents are skipped entirely.
+const RAW_TAGS: [&str; 2] = ["script", "style"];
+
+/// An element that has been opened but not closed yet: its name, attributes and children.
+type OpenElement = (String, Vec<(String, String)>, Vec<Node>);
+
+#[derive(Debug, PartialEq)]
+enum Node {
+    Text(String),
+    Element {
+        name: String,
+        attrs: Vec<(String, String)>,
+        children: Vec<Node>,
+    },
+}
+
+impl Node {
+    fn name(&self) -> Option<&str> {
+        match self {
+            Node::Element { name, .. } => Some(name),
+            Node::Text(_) => None,
+        }
+    }
+
+    fn attr(&self, key: &str) -> Option<&str> {
+        match self {
+            Node::Element { attrs, .. } => attrs
+                .iter()
+                .find(|(k, _)| k == key)
+                .map(|(_, v)| v.as_str()),
+            Node::Text(_) => None,
+        }
+    }
+
+    fn children(&self) -> &[Node] {
+        match self {
+            Node::Element { children, .. } => children,
+            Node::Text(_) => &[],
+        }
+    }
+
+    /// The decoded text of this node and all of its descendants.
+    fn text(&self) -> String {
+        match self {
+            Node::Text(text) => text.clone(),
+            Node::Element { children, .. } => children.iter().map(Node::text).collect(),
+        }
+    }
+}
+
+/// Convert the `<article>` elements of `html` to markdown.
+/// Relative links are resolved against `base_url`.
+pub fn articles_to_markdown(html: &str, base_url: &str) -> String {
+    let nodes = parse(html);
+
+    let mut articles = vec![];
+    find_all(&nodes, "article", &mut articles);
+
+    let blocks: Vec<String> = articles
+        .iter()
+        .flat_map(|article| render_blocks(article.children(), base_url))
+        .collect();
+
+    if blocks.is_empty() {
+        String::new()
+    } else {
+        blocks.join("\n\n") + "\n"
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn parse(html: &str) -> Vec<Node> {
+    // stack of open elements. the bottom entry collects the top-level nodes.
+    let mut stack: Vec<OpenElement> = vec![(String::new(), vec![], vec![])];
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let Some(start) = rest.find('<') else {
+            push_text(&mut stack, rest);
+            break;
+        };
+
+        push_text(&mut stack, &rest[..start]);
+        rest = &rest[start..];
+
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+            continue;
+        }
+
+        let Some(end) = rest.find('>') else {
+            push_text(&mut stack, rest);
+            break;
+        };
+
+        let tag = &rest[1..end];
+        rest = &rest[end + 1..];
+
+        if tag.starts_with('!') || tag.starts_with('?') {
+            continue;
+        }
+
+        if let Some(name) = tag.strip_prefix('/') {
+            let name = name.trim().to_ascii_lowercase();
+            // ignore stray closing tags, and close any unclosed children of a matching tag.
+            if let Some(pos) = stack.iter().skip(1).rposition(|(n, _, _)| *n == name) {
+                while stack.len() > pos + 1 {
+                    close_element(&mut stack);
+                }
+            }
+            continue;
+        }
+
+        let self_closing = tag.ends_with('/');
+        let tag = tag.trim_end_matches('/');
+        let name_end = tag.find(char::is_whitespace).unwrap_or(tag.len());
+        let name = tag[..name_end].to_ascii_lowercase();
+        let attrs = parse_attrs(&tag[name_end..]);
+
+        if RAW_TAGS.contains(&name.as_str()) {
+            let closing = format!("</{name}");
+            rest = rest
+                .find(&closing)
+                .and_then(|pos| rest[pos..].find('>').map(|end| &rest[pos + end + 1..]))
+                .unwrap_or("");
+            continue;
+        }
+
+        if self_closing || VOID_TAGS.contains(&name.as_str()) {
+            let element = Node::Element {
+                name,
+                attrs,
+                children: vec![],
+            };
+            stack.last_mut().unwrap().2.push(element);
+        } else {
+            stack.push((name, attrs, vec![]));
+        }
+    }
+
+    while stack.len() > 1 {
+        close_element(&mut stack);
+    }
+
+    stack.pop().map(|(_, _, nodes)| nodes).unwrap_or_default()
+}
+
+fn push_text(stack: &mut [OpenElement], text: &str) {
+    if !text.is_empty() {
+        stack
+            .last_mut()
+            .unwrap()
+            .2
+            .push(Node::Text(decode_entities(text)));
+    }
+}
+
+fn close_element(stack: &mut Vec<OpenElement>) {
+    let (name, attrs, children) = stack.pop().unwrap();
+    stack.last_mut().unwrap().2.push(Node::Element {
+        name,
+        attrs,
+        children,
+    });
+}
+
+fn parse_attrs(s: &str) -> Vec<(String, String)> {
+    let mut attrs = vec![];
+    let mut rest = s.trim_start();
+
+    while !rest.is_empty() {
+        let key_end = rest
+            .find(|c: char| c == '=' || c.is_whitespace())
+            .unwrap_or(rest.len());
+        let key = rest[..key_end].to_ascii_lowercase();
+        rest = rest[key_end..].trim_start();
+
+        let value = if let Some(value) = rest.strip_prefix('=') {
+            let value = value.trim_start();
+            let (value, remaining) = match value.chars().next() {
+                Some(quote @ ('"' | '\'')) => {
+                    let value = &value[1..];
+                    let end = value.find(quote).unwrap_or(value.len());
+                    (&value[..end], value.get(end + 1..).unwrap_or(""))
+                }
+                _ => {
+                    let end = value.find(char::is_whitespace).unwrap_or(value.len());
+                    (&value[..end], &value[end..])
+                }
+            };
+            rest = remaining.trim_start();
+            decode_entities(value)
+        } else {
+            String::new()
+        };
+
+        if !key.is_empty() {
+            attrs.push((key, value));
+        }
+    }
+
+    attrs
+}
+
+fn decode_entities(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let decoded = rest.find(';').and_then(|end| {
+            let entity = &rest[1..end];
+            let c = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                _ => entity
+                    .strip_prefix("#x")
+                    .or_else(|| entity.strip_prefix("#X"))
+                    .map(|hex| u32::from_str_radix(hex, 16))
+                    .or_else(|| entity.strip_prefix('#').map(str::parse))
+                    .and_then(Result::ok)
+                    .and_then(char::from_u32),
+            };
+            c.map(|c| (c, end))
+        });
+
+        match decoded {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+fn find_all<'a>(nodes: &'a [Node], name: &str, found: &mut Vec<&'a Node>) {
+    for node in nodes {
+        if node.name() == Some(name) {
+            found.push(node);
+        } else {
+            find_all(node.children(), name, found);
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn is_block(node: &Node) -> bool {
+    matches!(
+        node.name(),
+        Some(
+            "h1" | "h2"
+                | "h3"
+                | "h4"
+                | "h5"
+                | "h6"
+                | "p"
+                | "pre"
+                | "ul"
+                | "ol"
+                | "blockquote"
+                | "div"
+                | "section"
+                | "article"
+                | "main"
+                | "hr"
+        )
+    )
+}
+
+fn render_blocks(nodes: &[Node], base_url: &str) -> Vec<String> {
+    let mut blocks = vec![];
+    let mut inline: Vec<&Node> = vec![];
+
+    let flush = |inline: &mut Vec<&Node>, blocks: &mut Vec<String>| {
+        let text: String = inline.iter().map(|n| render_inline(n, base_url)).collect();
+        let text = text.trim();
+        if !text.is_empty() {
+            blocks.push(text.to_string());
+        }
+        inline.clear();
+    };
+
+    for node in nodes {
+        if !is_block(node) {
+            inline.push(node);
+            continue;
+        }
+
+        flush(&mut inline, &mut blocks);
+
+        match node.name() {
+            Some(heading @ ("h1" | "h2" | "h3" | "h4" | "h5" | "h6")) => {
+                let level: usize = heading[1..].parse().unwrap();
+                let text = render_inline_all(node.children(), base_url);
+                blocks.push(format!("{} {}", "#".repeat(level), text.trim()));
+            }
+            Some("p") => {
+                let text = render_inline_all(node.children(), base_url);
+                if !text.trim().is_empty() {
+                    blocks.push(text.trim().to_string());
+                }
+            }
+            Some("pre") => {
+                let text = node.text();
+                blocks.push(format!("```\n{}\n```", text.trim_end_matches('\n')));
+            }
+            Some("ul" | "ol") => blocks.push(render_list(node, 0, base_url).join("\n")),
+            Some("blockquote") => {
+                let quoted = render_blocks(node.children(), base_url).join("\n\n");
+                let lines: Vec<String> = quoted
+                    .lines()
+                    .map(|line| format!("> {line}").trim_end().to_string())
+                    .collect();
+                blocks.push(lines.join("\n"));
+            }
+            Some("hr") => blocks.push("---".into()),
+            _ => blocks.extend(render_blocks(node.children(), base_url)),
+        }
+    }
+
+    flush(&mut inline, &mut blocks);
+    blocks
+}
+
+fn render_list(list: &Node, depth: usize, base_url: &str) -> Vec<String> {
+    let indent = "  ".repeat(depth);
+    let ordered = list.name() == Some("ol");
+    let mut lines = vec![];
+
+    for (i, item) in list
+        .children()
+        .iter()
+        .filter(|n| n.name() == Some("li"))
+        .enumerate()
+    {
+        let marker = if ordered {
+            format!("{}.", i + 1)
+        } else {
+            "-".into()
+        };
+
+        let (nested, content): (Vec<&Node>, Vec<&Node>) = item
+            .children()
+            .iter()
+            .partition(|n| matches!(n.name(), Some("ul" | "ol")));
+
+        let text: String = content.iter().map(|n| render_inline(n, base_url)).collect();
+        lines.push(format!("{indent}{marker} {}", text.trim()));
+
+        for list in nested {
+            lines.extend(render_list(list, depth + 1, base_url));
+        }
+    }
+
+    lines
+}
+
+fn render_inline_all(nodes: &[Node], base_url: &str) -> String {
+    nodes.iter().map(|n| render_inline(n, base_url)).collect()
+}
+
+fn render_inline(node: &Node, base_url: &str) -> String {
+    let Node::Element { name, children, .. } = node else {
+        return collapse_whitespace(&node.text());
+    };
+
+    match name.as_str() {
+        "em" | "i" => wrap("*", &render_inline_all(children, base_url)),
+        "strong" | "b" => wrap("**", &render_inline_all(children, base_url)),
+        "code" => {
+            let code = format!("`{}`", node.text());
+            // AoC highlights answers in examples as `<code><em>42</em></code>`.
+            match children.as_slice() {
+                [em] if em.name() == Some("em") => format!("*{code}*"),
+                _ => code,
+            }
+        }
+        "a" => {
+            let text = render_inline_all(children, base_url);
+            match node.attr("href") {
+                Some(href) => format!("[{text}]({})", resolve_url(href, base_url)),
+                None => text,
+            }
+        }
+        "br" => "\n".into(),
+        _ => render_inline_all(children, base_url),
+    }
+}
+
+/// Wrap `text` in a markdown delimiter, keeping surrounding whitespace outside of it.
+fn wrap(delimiter: &str, text: &str) -> String {
+    let trimmed = text.trim();
+    if trimmed.is_empty() {
+        return text.to_string();
+    }
+    let leading = if text.starts_with(char::is_whitespace) {
+        " "
+    } else {
+        ""
+    };
+    let trailing = if text.ends_with(char::is_whitespace) {
+        " "
+    } else {
+        ""
+    };
+    format!("{leading}{delimiter}{trimmed}{delimiter}{trailing}")
+}
+
+fn collapse_whitespace(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut in_space = false;
+    for c in s.chars() {
+        if c.is_whitespace() {
+            if !in_space {
+                out.push(' ');
+            }
+            in_space = true;
+        } else {
+            out.push(c);
+            in_space = false;
+        }
+    }
+    out
+}
+
+fn resolve_url(href: &str, base_url: &str) -> String {
+    if href.starts_with('/') && !href.starts_with("//") {
+        format!("{}{href}", base_url.trim_end_matches('/'))
+    } else {
+        href.to_string()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::articles_to_markdown;
+
+    const BASE_URL: &str = "https://adventofcode.com";
+
+    #[test]
+    fn converts_puzzle_article() {
+        let html = r#"<!DOCTYPE html>
+<html><head><script>var x = "<article>";</script></head>
+<body><main>
+<article class="day-desc"><h2>--- Day 1: Inverse Captcha ---</h2>
+<p>The captcha requires you to review a sequence of digits (your puzzle input) and find the <em>sum</em> of all digits.</p>
+<p>For example:</p>
+<ul>
+<li><code>1122</code> produces a sum of <code>3</code>.</li>
+<li><code>91212129</code> produces <code><em>9</em></code> because the only digit that matches is the last digit, <code>9</code>.</li>
+</ul>
+<pre><code>a &lt;-&gt; b
+c
+</code></pre>
+<p>See <a href="/2017/about">about</a> &amp; <a href="https://example.com" target="_blank">this</a>.</p>
+</article>
+<p>Your puzzle answer was <code>1044</code>.</p>
+</main></body></html>"#;
+
+        let expected = [
+            "## --- Day 1: Inverse Captcha ---",
+            "",
+            "The captcha requires you to review a sequence of digits (your puzzle input) and find the *sum* of all digits.",
+            "",
+            "For example:",
+            "",
+            "- `1122` produces a sum of `3`.",
+            "- `91212129` produces *`9`* because the only digit that matches is the last digit, `9`.",
+            "",
+            "```",
+            "a <-> b",
+            "c",
+            "```",
+            "",
+            "See [about](https://adventofcode.com/2017/about) & [this](https://example.com).",
+            "",
+        ]
+        .join("\n");
+
+        assert_eq!(articles_to_markdown(html, BASE_URL), expected);
+    }
+
+    #[test]
+    fn converts_multiple_articles_and_nested_lists() {
+        let html = "<article><p>One</p></article><p>x</p>\
+            <article><ul><li>a<ul><li>b</li></ul></li></ul></article>";
+        assert_eq!(articles_to_markdown(html, BASE_URL), "One\n\n- a\n  - b\n");
+    }
+
+    #[test]
+    fn decodes_entities() {
+        let html = "<article><p>&#39;x&#x27; &quot;y&quot; &unknown; &</p></article>";
+        assert_eq!(
+            articles_to_markdown(html, BASE_URL),
+            "'x' \"y\" &unknown; &\n"
+        );
+    }
+
+    #[test]
+    fn handles_missing_articles() {
+        assert_eq!(articles_to_markdown("<p>404 Not Found</p>", BASE_URL), "");
+    }
+}
@@ -0,0 +1,371 @@
+//! Rendering of puzzle descriptions in the terminal.
+//!
+//! This only understands the markdown written by [`super::html`]: headings, paragraphs, lists,
+//! blockquotes, fenced code blocks and the inline `*em*`, `**strong**`, `` `code` `` and
+//! `[text](url)` markers. Highlighted code (`` *`42`* ``) is how the puzzles mark answers.
+use std::env;
+
+use crate::template::{ANSI_BOLD, ANSI_RESET};
+
+const ANSI_CODE: &str = "\x1b[36m";
+const ANSI_HIGHLIGHT: &str = "\x1b[1;33m";
+const ANSI_UNDERLINE: &str = "\x1b[4m";
+const ANSI_DIM: &str = "\x1b[2m";
+
+const DEFAULT_WIDTH: usize = 80;
+/// Long lines are hard to read, even if the terminal is wide enough for them.
+const MAX_WIDTH: usize = 100;
+
+/// Heading that starts the description of part two.
+const PART_TWO_HEADING: &str = "## --- Part Two ---";
+
+/// The width to wrap text to, taken from `$COLUMNS` if set.
+pub fn terminal_width() -> usize {
+    env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.trim().parse().ok())
+        .filter(|&columns| columns > 0)
+        .map_or(DEFAULT_WIDTH, |columns: usize| columns.min(MAX_WIDTH))
+}
+
+/// The description of a single part, or the whole puzzle if `part` is `None`.
+/// Returns `None` if the description of part two is not present.
+pub fn select_part(markdown: &str, part: Option<u8>) -> Option<&str> {
+    let split = markdown
+        .find(&format!("\n{PART_TWO_HEADING}"))
+        .map(|i| i + 1)
+        .or_else(|| markdown.starts_with(PART_TWO_HEADING).then_some(0));
+
+    match (part, split) {
+        (None, _) | (Some(1), None) => Some(markdown),
+        (Some(1), Some(i)) => Some(&markdown[..i]),
+        (Some(_), Some(i)) => Some(&markdown[i..]),
+        (Some(_), None) => None,
+    }
+}
+
+/// The title of a puzzle, taken from its `## --- Day 1: Title ---` heading.
+pub fn puzzle_title(markdown: &str) -> Option<&str> {
+    let heading = markdown.lines().find_map(heading)?;
+    let (_, title) = heading
+        .trim_matches(|c| c == '-' || c == ' ')
+        .split_once(": ")?;
+    Some(title.trim()).filter(|title| !title.is_empty())
+}
+
+/// Render `markdown` with ANSI styles, wrapping text to `width` columns.
+pub fn render(markdown: &str, width: usize) -> String {
+    let mut out: Vec<String> = vec![];
+    let mut paragraph: Vec<&str> = vec![];
+    let mut lines = markdown.lines();
+
+    let flush = |paragraph: &mut Vec<&str>, out: &mut Vec<String>| {
+        if !paragraph.is_empty() {
+            out.extend(wrap(&parse_inline(&paragraph.join(" ")), width, "", ""));
+            paragraph.clear();
+        }
+    };
+
+    while let Some(line) = lines.next() {
+        if line.trim().is_empty() {
+            flush(&mut paragraph, &mut out);
+            out.push(String::new());
+        } else if line.starts_with("```") {
+            flush(&mut paragraph, &mut out);
+            for code in lines.by_ref().take_while(|l| !l.starts_with("```")) {
+                out.push(format!("    {ANSI_CODE}{code}{ANSI_RESET}"));
+            }
+        } else if let Some(heading) = heading(line) {
+            flush(&mut paragraph, &mut out);
+            out.push(format!("{ANSI_BOLD}{heading}{ANSI_RESET}"));
+        } else if line.trim() == "---" {
+            flush(&mut paragraph, &mut out);
+            out.push(format!("{ANSI_DIM}{}{ANSI_RESET}", "─".repeat(width)));
+        } else if let Some((indent, marker, item)) = list_item(line) {
+            flush(&mut paragraph, &mut out);
+            let first = format!("{indent}{marker} ");
+            let rest = " ".repeat(first.chars().count());
+            out.extend(wrap(&parse_inline(item), width, &first, &rest));
+        } else if let Some(quote) = line.strip_prefix('>') {
+            flush(&mut paragraph, &mut out);
+            let prefix = format!("{ANSI_DIM}│{ANSI_RESET} ");
+            out.extend(wrap(&parse_inline(quote.trim()), width, &prefix, &prefix));
+        } else {
+            paragraph.push(line.trim());
+        }
+    }
+
+    flush(&mut paragraph, &mut out);
+
+    // Collapse the blank lines left between blocks.
+    out.dedup_by(|a, b| a.is_empty() && b.is_empty());
+    while out.last().is_some_and(String::is_empty) {
+        out.pop();
+    }
+
+    out.join("\n") + "\n"
+}
+
+fn heading(line: &str) -> Option<&str> {
+    let text = line.trim_start_matches('#');
+    (text.len() < line.len() && text.starts_with(' ')).then(|| text.trim())
+}
+
+/// Split a list item into its indentation, marker and contents.
+fn list_item(line: &str) -> Option<(&str, &str, &str)> {
+    let content = line.trim_start();
+    let indent = &line[..line.len() - content.len()];
+    let (marker, item) = content.split_once(' ')?;
+
+    let is_marker = marker == "-"
+        || marker
+            .strip_suffix('.')
+            .is_some_and(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()));
+
+    is_marker.then_some((indent, marker, item))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A run of text with a single style.
+#[derive(Debug, PartialEq)]
+struct Span {
+    text: String,
+    style: &'static str,
+}
+
+fn parse_inline(text: &str) -> Vec<Span> {
+    let mut spans = vec![];
+    let mut plain = String::new();
+    let mut rest = text;
+
+    let push = |spans: &mut Vec<Span>, plain: &mut String, span: Option<Span>| {
+        if !plain.is_empty() {
+            spans.push(Span {
+                text: std::mem::take(plain),
+                style: "",
+            });
+        }
+        spans.extend(span);
+    };
+
+    while let Some(c) = rest.chars().next() {
+        let styled = if let Some(inner) = delimited(rest, "*`", "`*") {
+            Some((inner, ANSI_HIGHLIGHT, inner.len() + 4))
+        } else if let Some(inner) = delimited(rest, "**", "**") {
+            Some((inner, ANSI_BOLD, inner.len() + 4))
+        } else if let Some(inner) = delimited(rest, "`", "`") {
+            Some((inner, ANSI_CODE, inner.len() + 2))
+        } else if let Some(inner) =
+            delimited(rest, "*", "*").filter(|inner| !inner.starts_with(' '))
+        {
+            Some((inner, ANSI_BOLD, inner.len() + 2))
+        } else {
+            link(rest).map(|(text, len)| (text, ANSI_UNDERLINE, len))
+        };
+
+        match styled {
+            Some((inner, style, len)) => {
+                let span = Span {
+                    text: inner.to_string(),
+                    style,
+                };
+                push(&mut spans, &mut plain, Some(span));
+                rest = &rest[len..];
+            }
+            None => {
+                plain.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+
+    push(&mut spans, &mut plain, None);
+    spans
+}
+
+/// The text between `open` and the next `close`, if `s` starts with `open`.
+fn delimited<'a>(s: &'a str, open: &str, close: &str) -> Option<&'a str> {
+    let s = s.strip_prefix(open)?;
+    let end = s.find(close)?;
+    (end > 0).then(|| &s[..end])
+}
+
+/// The text of a `[text](url)` link and the length of its markdown.
+fn link(s: &str) -> Option<(&str, usize)> {
+    let text = delimited(s, "[", "]")?;
+    let url = delimited(&s[text.len() + 2..], "(", ")")?;
+    Some((text, text.len() + url.len() + 4))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Wrap `spans` to lines of at most `width` columns, breaking at whitespace.
+/// The first line starts with `first_prefix`, all others with `prefix`. Both prefixes must have
+/// the same width once styles are removed.
+fn wrap(spans: &[Span], width: usize, first_prefix: &str, prefix: &str) -> Vec<String> {
+    let indent = visible_width(first_prefix);
+    let width = width.saturating_sub(indent).max(1);
+
+    // Split the spans into words, a word can consist of several differently styled parts.
+    let mut words: Vec<Vec<(&str, &'static str)>> = vec![vec![]];
+    for span in spans {
+        let mut parts = span.text.split(' ').peekable();
+        while let Some(part) = parts.next() {
+            if !part.is_empty() {
+                words.last_mut().unwrap().push((part, span.style));
+            }
+            if parts.peek().is_some() && !words.last().unwrap().is_empty() {
+                words.push(vec![]);
+            }
+        }
+    }
+
+    let mut lines = vec![];
+    let mut line = String::new();
+    let mut line_width = 0;
+
+    for word in words.iter().filter(|w| !w.is_empty()) {
+        let word_width: usize = word.iter().map(|(text, _)| text.chars().count()).sum();
+
+        if line_width > 0 && line_width + 1 + word_width > width {
+            lines.push(std::mem::take(&mut line));
+            line_width = 0;
+        }
+
+        if line_width > 0 {
+            line.push(' ');
+            line_width += 1;
+        }
+
+        for (text, style) in word {
+            if style.is_empty() {
+                line.push_str(text);
+            } else {
+                line.push_str(&format!("{style}{text}{ANSI_RESET}"));
+            }
+        }
+        line_width += word_width;
+    }
+
+    if line_width > 0 {
+        lines.push(line);
+    }
+
+    lines
+        .into_iter()
+        .enumerate()
+        .map(|(i, line)| {
+            let prefix = if i == 0 { first_prefix } else { prefix };
+            format!("{prefix}{line}")
+        })
+        .collect()
+}
+
+fn visible_width(s: &str) -> usize {
+    let mut width = 0;
+    let mut in_escape = false;
+    for c in s.chars() {
+        match c {
+            '\x1b' => in_escape = true,
+            'm' if in_escape => in_escape = false,
+            _ if !in_escape => width += 1,
+            _ => {}
+        }
+    }
+    width
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_inline, puzzle_title, render, select_part, Span, ANSI_CODE, ANSI_HIGHLIGHT};
+    use crate::template::{ANSI_BOLD, ANSI_RESET};
+
+    const PUZZLE: &str =
+        "## --- Day 1: Inverse Captcha ---\n\nOne.\n\n## --- Part Two ---\n\nTwo.\n";
+
+    fn strip_ansi(s: &str) -> String {
+        let mut out = String::new();
+        let mut in_escape = false;
+        for c in s.chars() {
+            match c {
+                '\x1b' => in_escape = true,
+                'm' if in_escape => in_escape = false,
+                _ if !in_escape => out.push(c),
+                _ => {}
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn selects_parts() {
+        assert_eq!(select_part(PUZZLE, None), Some(PUZZLE));
+        assert_eq!(
+            select_part(PUZZLE, Some(1)),
+            Some("## --- Day 1: Inverse Captcha ---\n\nOne.\n\n")
+        );
+        assert_eq!(
+            select_part(PUZZLE, Some(2)),
+            Some("## --- Part Two ---\n\nTwo.\n")
+        );
+
+        let part_one = "## --- Day 1: Inverse Captcha ---\n\nOne.\n";
+        assert_eq!(select_part(part_one, Some(1)), Some(part_one));
+        assert_eq!(select_part(part_one, Some(2)), None);
+    }
+
+    #[test]
+    fn finds_titles() {
+        assert_eq!(puzzle_title(PUZZLE), Some("Inverse Captcha"));
+        assert_eq!(puzzle_title("Some text.\n\n## --- Part Two ---\n"), None);
+        assert_eq!(puzzle_title(""), None);
+    }
+
+    #[test]
+    fn parses_inline_styles() {
+        let span = |text: &str, style| Span {
+            text: text.into(),
+            style,
+        };
+
+        assert_eq!(
+            parse_inline("a *sum* of `3` is *`9`*, see [about](https://x.com) * 2"),
+            vec![
+                span("a ", ""),
+                span("sum", ANSI_BOLD),
+                span(" of ", ""),
+                span("3", ANSI_CODE),
+                span(" is ", ""),
+                span("9", ANSI_HIGHLIGHT),
+                span(", see ", ""),
+                span("about", super::ANSI_UNDERLINE),
+                span(" * 2", ""),
+            ]
+        );
+    }
+
+    #[test]
+    fn renders_blocks() {
+        let markdown = "## --- Day 1: Test ---\n\nThe *sum*.\n\n- a\n  - b\n\n```\nx  y\n```\n";
+        let rendered = render(markdown, 80);
+
+        assert!(rendered.starts_with(&format!("{ANSI_BOLD}--- Day 1: Test ---{ANSI_RESET}\n")));
+        assert!(rendered.contains(&format!("{ANSI_CODE}x  y{ANSI_RESET}")));
+        assert_eq!(
+            strip_ansi(&rendered),
+            "--- Day 1: Test ---\n\nThe sum.\n\n- a\n  - b\n\n    x  y\n"
+        );
+    }
+
+    #[test]
+    fn wraps_to_width() {
+        let markdown = "one two three *four* five\n\n- six seven eight nine\n";
+        assert_eq!(
+            strip_ansi(&render(markdown, 10)),
+            "one two\nthree four\nfive\n\n- six\n  seven\n  eight\n  nine\n"
+        );
+    }
+}
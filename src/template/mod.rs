@@ -1,25 +1,76 @@
-use std::{env, fs};
+use std::{
+    fs,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
-pub mod aoc_cli;
+pub mod alloc_stats;
+pub mod aoc_client;
 pub mod commands;
+pub mod config;
+pub mod examples;
+pub mod params;
+pub mod report;
 pub mod runner;
 
 pub use day::*;
+pub use formats::Format;
+pub use puzzle::*;
+pub use registry::{Registry, Solution};
+pub use run_multi::RunLimits;
 
+mod answers;
+mod dashboard;
 mod day;
+mod extract;
+mod formats;
+mod history;
+mod html;
+mod markdown;
+mod puzzle;
 mod readme_benchmarks;
+mod registry;
 mod run_multi;
+mod stats;
+mod submissions;
+mod templates;
 mod timings;
+mod watch;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
-/// Helper function that reads a text file to a string.
+#[cfg(feature = "dhat-heap")]
+#[global_allocator]
+static ALLOC: dhat::Alloc = dhat::Alloc;
+
+#[cfg(all(feature = "alloc-stats", not(feature = "dhat-heap")))]
+#[global_allocator]
+static ALLOC: alloc_stats::CountingAllocator = alloc_stats::CountingAllocator;
+
+/// Current time in seconds since the unix epoch.
+pub(crate) fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// The configured directory of `folder`, e.g. `inputs` or `examples`.
+fn data_folder(folder: &str) -> PathBuf {
+    let paths = &config::get().paths;
+    match folder {
+        "inputs" => paths.inputs.clone(),
+        "examples" => paths.examples.clone(),
+        "puzzles" => paths.puzzles.clone(),
+        _ => paths.data.join(folder),
+    }
+}
+
+/// Helper function that reads a text file of the primary year to a string.
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let filepath = data_folder(folder).join(format!("{day}.txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
@@ -27,42 +78,193 @@ pub fn read_file(folder: &str, day: Day) -> String {
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
 pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
+    let filepath = data_folder(folder).join(format!("{day}-{part}.txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
-/// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// The year is taken from the file name: solutions in `src/bin/YYYY-NN.rs` belong to that year,
+/// solutions in `src/bin/NN.rs` to the primary year.
+///
+/// The optional, second parameter (1 or 2) declares a day with a single part, e.g. day 25.
+/// Other parts are not run, benched, verified or submitted.
+///
+/// An optional `parse = <fn>` parameter runs the given function once on the input and passes
+/// a reference to its output to each part. Parsing is timed separately from the parts.
+/// Use `try_parse = <fn>` instead if the parse function returns a `Result`.
+///
+/// An optional `params { <name>: <type> = <default>, ... }` block, placed before `parse`,
+/// declares typed parameters, e.g. an iteration count that differs between the examples and the
+/// real input. They are collected in a `Params` struct that is passed to each part as a second
+/// argument. Defaults apply to the real input, and can be overridden by the `params` of an
+/// example in the manifest or with `--param <name>=<value>`.
+///
+/// Parts may return `Option<T>`, `Result<T, E>` or `Result<Option<T>, E>`. If a part fails,
+/// the binary exits with a non-zero status after running the remaining parts.
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+    ($day:expr $(, $parse_kind:ident = $parse:expr)?) => {
+        $crate::solution!(@impl $day, [$($parse_kind $parse)?] [] [part_one, 1] [part_two, 2]);
+    };
+    ($day:expr, 1 $(, $parse_kind:ident = $parse:expr)?) => {
+        $crate::solution!(@impl $day, [$($parse_kind $parse)?] [] [part_one, 1]);
+    };
+    ($day:expr, 2 $(, $parse_kind:ident = $parse:expr)?) => {
+        $crate::solution!(@impl $day, [$($parse_kind $parse)?] [] [part_two, 2]);
+    };
+    ($day:expr, params { $($params:tt)* } $(, $parse_kind:ident = $parse:expr)?) => {
+        $crate::solution!(@impl $day, [$($parse_kind $parse)?] [$($params)*] [part_one, 1] [part_two, 2]);
+    };
+    ($day:expr, 1, params { $($params:tt)* } $(, $parse_kind:ident = $parse:expr)?) => {
+        $crate::solution!(@impl $day, [$($parse_kind $parse)?] [$($params)*] [part_one, 1]);
+    };
+    ($day:expr, 2, params { $($params:tt)* } $(, $parse_kind:ident = $parse:expr)?) => {
+        $crate::solution!(@impl $day, [$($parse_kind $parse)?] [$($params)*] [part_two, 2]);
+    };
+
+    (@parse parse, $parse:expr, $input:ident, $puzzle:ident, $options:ident) => {
+        run_parse($parse, &$input, $puzzle, $options)
+    };
+    (@parse try_parse, $parse:expr, $input:ident, $puzzle:ident, $options:ident) => {
+        match run_try_parse($parse, &$input, $puzzle, $options) {
+            Some(parsed) => parsed,
+            None => return false,
+        }
     };
-    ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+
+    (@example_parse parse, $parse:expr, $input:ident) => {
+        $parse(&$input)
+    };
+    (@example_parse try_parse, $parse:expr, $input:ident) => {
+        match $parse(&$input) {
+            Ok(parsed) => parsed,
+            Err(e) => return Outcome::Failed(e.into()),
+        }
+    };
+
+    (@params_type []) => {
+        $crate::template::params::NoParams
     };
-    ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+    (@params_type [$($params:tt)+]) => {
+        Params
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (@params_struct []) => {};
+    (@params_struct [$($name:ident : $type:ty = $default:expr),+ $(,)?]) => {
+        /// Parameters of this day, passed to each part.
+        #[derive(Clone, Debug)]
+        pub struct Params {
+            $( pub $name: $type, )+
+        }
+
+        impl Default for Params {
+            fn default() -> Self {
+                Self {
+                    $( $name: $default, )+
+                }
+            }
+        }
+
+        impl $crate::template::params::SolutionParams for Params {
+            fn set(
+                &mut self,
+                key: &str,
+                value: &str,
+            ) -> Result<(), $crate::template::params::ParamError> {
+                match key {
+                    $( stringify!($name) => {
+                        self.$name = $crate::template::params::parse_value(key, value)?;
+                    } )+
+                    _ => return Err($crate::template::params::ParamError::Unknown(key.into())),
+                }
+                Ok(())
+            }
+        }
+    };
+
+    (@call [] $func:expr, $input:expr, $params:ident) => {
+        $func($input)
+    };
+    (@call [$($params:tt)+] $func:expr, $input:expr, $params_ident:ident) => {
+        $func($input, &$params_ident)
+    };
+
+    (@impl $day:expr, [$($parse_kind:ident $parse:expr)?] $params:tt $( [$func:expr, $part:expr] )*) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
+        /// The year of the current day, `None` for the primary year.
+        const YEAR: Option<u16> = $crate::template::Puzzle::__bin_year(file!());
 
-        #[cfg(feature = "dhat-heap")]
-        #[global_allocator]
-        static ALLOC: dhat::Alloc = dhat::Alloc;
+        $crate::solution!(@params_struct $params);
 
+        /// Runs this day, either from its own binary or in-process via the registry.
+        pub const SOLUTION: $crate::template::Solution =
+            $crate::template::Solution::__new(YEAR, DAY, &[$($part),*], |options| {
+                use $crate::template::runner::*;
+                let puzzle = $crate::template::Puzzle::in_year(YEAR, DAY);
+                #[allow(unused_variables)]
+                let Some(params) = $crate::template::params::resolve::<
+                    $crate::solution!(@params_type $params),
+                >(puzzle, options) else {
+                    return false;
+                };
+                let Some(input) = read_input(puzzle, options) else {
+                    return false;
+                };
+                $( let input = $crate::solution!(@parse $parse_kind, $parse, input, puzzle, options); )?
+                let mut ok = true;
+                $(
+                    ok &= run_part(
+                        |i| $crate::solution!(@call $params $func, i, params),
+                        &input,
+                        puzzle,
+                        $part,
+                        options,
+                    ) != Status::Failed;
+                )*
+                ok
+            });
+
+        #[allow(dead_code)]
         fn main() {
-            use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            if !SOLUTION.run(&$crate::template::runner::RunOptions::from_args()) {
+                std::process::exit(1);
+            }
+        }
+
+        /// Runs the examples listed in the manifest, e.g. `data/examples/NN.toml`.
+        #[cfg(test)]
+        #[test]
+        fn example_manifest() {
+            fn solve(
+                input: String,
+                part: u8,
+                overrides: &[$crate::template::params::Param],
+            ) -> $crate::template::runner::Outcome {
+                use $crate::template::params::SolutionParams;
+                use $crate::template::runner::*;
+                #[allow(unused_mut, unused_variables)]
+                let mut params = <$crate::solution!(@params_type $params)>::default();
+                if let Err(e) = params.apply(overrides) {
+                    return Outcome::Failed(e.into());
+                }
+                $( let input = $crate::solution!(@example_parse $parse_kind, $parse, input); )?
+                $(
+                    if part == $part {
+                        return $crate::solution!(@call $params $func, &input, params)
+                            .into_outcome();
+                    }
+                )*
+                Outcome::Unsolved
+            }
+
+            $crate::template::examples::check(
+                $crate::template::Puzzle::in_year(YEAR, DAY),
+                SOLUTION.parts,
+                solve,
+            );
         }
     };
 }
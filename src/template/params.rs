@@ -0,0 +1,201 @@
+/// Typed parameters of a solution, e.g. an iteration count that differs between the examples
+/// and the real input.
+///
+/// Parameters are declared in `solution!` with a default for the real input. The default can be
+/// overridden by the `params` of an example in the manifest, or with `--param key=value`.
+use std::{fmt::Display, str::FromStr};
+
+use crate::template::examples::Manifest;
+use crate::template::runner::{InputSource, RunOptions};
+use crate::template::{Puzzle, ANSI_BOLD, ANSI_RESET};
+
+/// A parameter value that overrides the default, e.g. `iterations=2`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Param {
+    pub key: String,
+    pub value: String,
+}
+
+impl FromStr for Param {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (key, value) = s
+            .split_once('=')
+            .ok_or(format!("expected `key=value`, got `{s}`"))?;
+
+        Ok(Self {
+            key: key.trim().into(),
+            value: value.trim().into(),
+        })
+    }
+}
+
+impl Display for Param {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}={}", self.key, self.value)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParamError {
+    Unknown(String),
+    Invalid {
+        key: String,
+        value: String,
+        error: String,
+    },
+}
+
+impl Display for ParamError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParamError::Unknown(key) => write!(f, "unknown parameter `{key}`"),
+            ParamError::Invalid { key, value, error } => {
+                write!(f, "invalid value `{value}` for parameter `{key}`: {error}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParamError {}
+
+/// The parameters of a solution, implemented by the `Params` struct that `solution!` generates.
+pub trait SolutionParams: Default {
+    /// Set the parameter `key` from its string representation.
+    fn set(&mut self, key: &str, value: &str) -> Result<(), ParamError>;
+
+    /// Apply `overrides` in order, later values win.
+    fn apply(&mut self, overrides: &[Param]) -> Result<(), ParamError> {
+        overrides
+            .iter()
+            .try_for_each(|param| self.set(&param.key, &param.value))
+    }
+}
+
+/// Parameters of a solution that does not declare any.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NoParams;
+
+impl SolutionParams for NoParams {
+    fn set(&mut self, key: &str, _value: &str) -> Result<(), ParamError> {
+        Err(ParamError::Unknown(key.into()))
+    }
+}
+
+/// Parse `value` as the type of a parameter, used by the `Params` struct of `solution!`.
+#[doc(hidden)]
+pub fn parse_value<T: FromStr>(key: &str, value: &str) -> Result<T, ParamError>
+where
+    T::Err: Display,
+{
+    value.parse().map_err(|e: T::Err| ParamError::Invalid {
+        key: key.into(),
+        value: value.into(),
+        error: e.to_string(),
+    })
+}
+
+/// Resolve the parameters of a run of `puzzle`. Defaults are overridden by the manifest entries of
+/// the example that is run, if any, and then by `--param`.
+/// Returns `None` and prints the error if a parameter is unknown or invalid.
+pub fn resolve<P: SolutionParams>(puzzle: Puzzle, options: &RunOptions) -> Option<P> {
+    let mut params = P::default();
+
+    let result = example_params(puzzle, &options.input).and_then(|example| {
+        params
+            .apply(&example)
+            .and_then(|()| params.apply(&options.params))
+            .map_err(|e| e.to_string())
+    });
+
+    match result {
+        Ok(()) => Some(params),
+        Err(e) => {
+            eprintln!("{ANSI_BOLD}Error:{ANSI_RESET} {e}");
+            None
+        }
+    }
+}
+
+fn example_params(puzzle: Puzzle, input: &InputSource) -> Result<Vec<Param>, String> {
+    let InputSource::Example(n) = input else {
+        return Ok(vec![]);
+    };
+
+    let file = match n {
+        Some(n) => format!("{}-{n}.txt", puzzle.day),
+        None => format!("{}.txt", puzzle.day),
+    };
+
+    Ok(Manifest::read(puzzle)?
+        .examples
+        .into_iter()
+        .filter(|example| example.file == file)
+        .flat_map(|example| example.params)
+        .collect())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_value, Param, ParamError, SolutionParams};
+
+    #[derive(Debug, Default, PartialEq)]
+    struct Params {
+        iterations: usize,
+        name: String,
+    }
+
+    impl SolutionParams for Params {
+        fn set(&mut self, key: &str, value: &str) -> Result<(), ParamError> {
+            match key {
+                "iterations" => self.iterations = parse_value(key, value)?,
+                "name" => self.name = parse_value(key, value)?,
+                _ => return Err(ParamError::Unknown(key.into())),
+            }
+            Ok(())
+        }
+    }
+
+    fn params(overrides: &[&str]) -> Vec<Param> {
+        overrides.iter().map(|p| p.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn parses_overrides() {
+        let param: Param = "iterations = 2".parse().unwrap();
+        assert_eq!(param.key, "iterations");
+        assert_eq!(param.value, "2");
+        assert_eq!(param.to_string(), "iterations=2");
+        assert!("iterations".parse::<Param>().is_err());
+    }
+
+    #[test]
+    fn applies_overrides() {
+        let mut p = Params::default();
+        p.apply(&params(&["iterations=2", "name=abc", "iterations=3"]))
+            .unwrap();
+        assert_eq!(
+            p,
+            Params {
+                iterations: 3,
+                name: "abc".into(),
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_overrides() {
+        let mut p = Params::default();
+        assert_eq!(
+            p.apply(&params(&["rounds=2"])),
+            Err(ParamError::Unknown("rounds".into()))
+        );
+        assert!(matches!(
+            p.apply(&params(&["iterations=-1"])),
+            Err(ParamError::Invalid { .. })
+        ));
+    }
+}
@@ -0,0 +1,206 @@
+/// A puzzle, identified by its year and day.
+///
+/// The primary year, `year` in `aoc.toml`, uses the single-year layout: solutions in
+/// `src/bin/NN.rs` and files in `data/`. Other years are scoped by their year, with solutions in
+/// `src/bin/YYYY-NN.rs` and files in `data/YYYY/`.
+use std::{fmt::Display, str::FromStr};
+
+use crate::template::{
+    config::{self, Paths},
+    Day, Registry,
+};
+
+/// The first year of Advent of Code.
+pub const FIRST_YEAR: u16 = 2015;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Puzzle {
+    pub year: u16,
+    pub day: Day,
+}
+
+impl Puzzle {
+    pub fn new(year: u16, day: Day) -> Self {
+        Self { year, day }
+    }
+
+    /// The puzzle of `day` in the primary year.
+    pub fn primary(day: Day) -> Self {
+        Self::new(config::get().year, day)
+    }
+
+    /// The puzzle of `day` in `year`, or in the primary year if `year` is `None`.
+    pub fn in_year(year: Option<u16>, day: Day) -> Self {
+        year.map_or_else(|| Self::primary(day), |year| Self::new(year, day))
+    }
+
+    pub fn is_primary(&self) -> bool {
+        self.year == config::get().year
+    }
+
+    /// The paths of this puzzle's year.
+    pub fn paths(&self) -> Paths {
+        config::get().paths_for(self.year)
+    }
+
+    /// The name of the solution's binary, `NN` in the primary year and `YYYY-NN` otherwise.
+    pub fn bin_name(&self) -> String {
+        if self.is_primary() {
+            self.day.to_string()
+        } else {
+            self.to_string()
+        }
+    }
+
+    /// The `--year` argument that selects this puzzle's year, empty for the primary year.
+    pub fn year_flag(&self) -> String {
+        if self.is_primary() {
+            String::new()
+        } else {
+            format!(" --year {}", self.year)
+        }
+    }
+
+    /// Label for output that may mix years: `Day NN`, prefixed with the year if `with_year`.
+    pub fn label(&self, with_year: bool) -> String {
+        if with_year {
+            format!("{} Day {}", self.year, self.day)
+        } else {
+            format!("Day {}", self.day)
+        }
+    }
+
+    // Not part of the public API, used by `solution!` to find the year of a binary.
+    #[doc(hidden)]
+    pub const fn __bin_year(path: &str) -> Option<u16> {
+        let bytes = path.as_bytes();
+
+        // the file name has to be `YYYY-NN.rs`.
+        let len = "YYYY-NN.rs".len();
+        if bytes.len() < len {
+            return None;
+        }
+        let start = bytes.len() - len;
+        if start > 0 && bytes[start - 1] != b'/' && bytes[start - 1] != b'\\' {
+            return None;
+        }
+
+        let mut year = 0;
+        let mut i = 0;
+        while i < len {
+            let b = bytes[start + i];
+            let valid = match i {
+                4 => b == b'-',
+                7 => b == b'.',
+                8 => b == b'r',
+                9 => b == b's',
+                _ => b.is_ascii_digit(),
+            };
+            if !valid {
+                return None;
+            }
+            if i < 4 {
+                year = year * 10 + (b - b'0') as u16;
+            }
+            i += 1;
+        }
+
+        Some(year)
+    }
+}
+
+impl Display for Puzzle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.year, self.day)
+    }
+}
+
+/// Parse a year of Advent of Code, e.g. for `--year`.
+pub fn parse_year(s: &str) -> Result<u16, String> {
+    s.parse()
+        .ok()
+        .filter(|year| *year >= FIRST_YEAR)
+        .ok_or_else(|| format!("expecting a year since {FIRST_YEAR}, got `{s}`"))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The years a command spans: a single year, or every year with solutions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Years {
+    One(u16),
+    All,
+}
+
+impl Years {
+    /// The primary year.
+    pub fn primary() -> Self {
+        Self::One(config::get().year)
+    }
+
+    /// The selected years, in order. [`Years::All`] includes the primary year, even if it has no
+    /// solutions yet.
+    pub fn list(self, registry: &Registry) -> Vec<u16> {
+        match self {
+            Self::One(year) => vec![year],
+            Self::All => {
+                let mut years = registry.years();
+                years.push(config::get().year);
+                years.sort_unstable();
+                years.dedup();
+                years
+            }
+        }
+    }
+}
+
+impl FromStr for Years {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "all" => Ok(Self::All),
+            s => parse_year(s)
+                .map(Self::One)
+                .map_err(|e| format!("{e} or `all`")),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_year, Puzzle, Years};
+    use crate::day;
+
+    #[test]
+    fn finds_bin_years() {
+        assert_eq!(Puzzle::__bin_year("src/bin/2016-01.rs"), Some(2016));
+        assert_eq!(
+            Puzzle::__bin_year("C:\\aoc\\src\\bin\\2016-25.rs"),
+            Some(2016)
+        );
+        assert_eq!(Puzzle::__bin_year("2015-03.rs"), Some(2015));
+        assert_eq!(Puzzle::__bin_year("src/bin/01.rs"), None);
+        assert_eq!(Puzzle::__bin_year("src/bin/x2016-01.rs"), None);
+        assert_eq!(Puzzle::__bin_year("src/bin/2016_01.rs"), None);
+    }
+
+    #[test]
+    fn parses_years() {
+        assert_eq!(parse_year("2016"), Ok(2016));
+        assert!(parse_year("16").is_err());
+        assert_eq!("all".parse(), Ok(Years::All));
+        assert_eq!("2017".parse(), Ok(Years::One(2017)));
+        assert!("every".parse::<Years>().is_err());
+    }
+
+    #[test]
+    fn displays_puzzles() {
+        let puzzle = Puzzle::new(2016, day!(4));
+        assert_eq!(puzzle.to_string(), "2016-04");
+        assert_eq!(puzzle.label(true), "2016 Day 04");
+        assert_eq!(puzzle.label(false), "Day 04");
+    }
+}
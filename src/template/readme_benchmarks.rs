@@ -1,11 +1,13 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+use std::{collections::BTreeMap, fs, io};
 
-use crate::template::timings::Timings;
-use crate::template::Day;
-
-static MARKER: &str = "<!--- benchmarking table --->";
+use crate::template::alloc_stats::format_bytes;
+use crate::template::config;
+use crate::template::report::PARSE_PART;
+use crate::template::stats::{format_nanos, BenchStats};
+use crate::template::timings::{Timing, Timings};
+use crate::template::{Puzzle, Registry};
 
 #[derive(Debug)]
 #[allow(dead_code)]
@@ -26,12 +28,12 @@ pub struct TablePosition {
 }
 
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_bin(puzzle: Puzzle) -> String {
+    format!("./src/bin/{}.rs", puzzle.bin_name())
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -52,76 +54,252 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+/// The table of a single year. The primary year's table is titled `Benchmarks`, other tables
+/// are prefixed with their year.
+fn construct_table(
+    prefix: &str,
+    year: u16,
+    timings: &Timings,
+    baseline: Option<&Timings>,
+    registry: &Registry,
+) -> String {
+    let header = if year == config::get().year {
+        format!("{prefix} Benchmarks")
+    } else {
+        format!("{prefix} {year} Benchmarks")
+    };
+
+    let mut lines: Vec<String> = vec![header, String::new()];
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+    // memory columns are only shown once timings were stored with the `alloc-stats` feature.
+    let with_alloc = timings.data.iter().any(|t| !t.alloc.is_empty());
+    let (alloc_header, alloc_align) = if with_alloc {
+        (" Peak memory | Allocations |", " :---: | :---: |")
+    } else {
+        ("", "")
+    };
 
-    for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
+    if baseline.is_some() {
+        lines.push(format!(
+            "| Day | Parse | Part 1 | Part 2 |{alloc_header} Δ |"
+        ));
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+            "| :---: | :---: | :---: | :---: |{alloc_align} :---: |"
+        ));
+    } else {
+        lines.push(format!("| Day | Parse | Part 1 | Part 2 |{alloc_header}"));
+        lines.push(format!("| :---: | :---: | :---: | :---:  |{alloc_align}"));
+    }
+
+    for timing in &timings.data {
+        let puzzle = Puzzle::new(year, timing.day);
+        let path = get_path_for_bin(puzzle);
+        let parts = registry.parts(puzzle);
+        let cell = |part: u8, stats: Option<&BenchStats>| {
+            if part != PARSE_PART && !parts.contains(&part) {
+                "n/a".into()
+            } else if timing.failed_parts.contains(&part) {
+                "✖ failed".into()
+            } else if let Some(limit) = timing
+                .exceeded
+                .filter(|_| part != PARSE_PART && stats.is_none())
+            {
+                // a part that did not finish before the day was stopped, parsing is optional.
+                format!("✖ {limit}")
+            } else {
+                format_part(stats)
+            }
+        };
+        let mut line = format!(
+            "| [Day {}]({}) | {} | {} | {} |",
             timing.day.into_inner(),
             path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+            cell(PARSE_PART, timing.parse.as_ref()),
+            cell(1, timing.part_1.as_ref()),
+            cell(2, timing.part_2.as_ref())
+        );
+        if with_alloc {
+            line += &format!(" {} |", format_alloc(timing));
+        }
+        if let Some(baseline) = baseline {
+            line += &format!(" {} |", format_delta(timing, baseline));
+        }
+        lines.push(line);
     }
 
     lines.push(String::new());
-    lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+    lines.push(format!("**Total: {:.2}ms**", timings.total_millis()));
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
-    s.replace_range(positions.pos_start..positions.pos_end, &table);
+/// Formats the median of a part, with spread and sample count for benched parts.
+fn format_part(stats: Option<&BenchStats>) -> String {
+    match stats {
+        None => "-".into(),
+        Some(s) if s.samples <= 1 => format!("`{}`", format_nanos(s.median)),
+        Some(s) => format!(
+            "`{}` ± {}<br><sub>{} … {}, p95 {}, n={}</sub>",
+            format_nanos(s.median),
+            format_nanos(s.std_dev),
+            format_nanos(s.min),
+            format_nanos(s.max),
+            format_nanos(s.p95),
+            s.samples
+        ),
+    }
+}
+
+/// Formats the peak memory of a day's parts, and the number and size of their allocations.
+fn format_alloc(timing: &Timing) -> String {
+    if timing.alloc.is_empty() {
+        return "- | -".into();
+    }
+
+    let peak = timing
+        .alloc
+        .values()
+        .map(|a| a.peak_bytes)
+        .max()
+        .unwrap_or(0);
+    let total: u64 = timing.alloc.values().map(|a| a.total_bytes).sum();
+    let count: u64 = timing.alloc.values().map(|a| a.count).sum();
+
+    format!(
+        "`{}` | {count} ({})",
+        format_bytes(peak),
+        format_bytes(total)
+    )
+}
+
+/// Formats the change of a day's total run time relative to `baseline`.
+fn format_delta(timing: &Timing, baseline: &Timings) -> String {
+    match baseline.data.iter().find(|t| t.day == timing.day) {
+        Some(before) if before.total_nanos > 0.0 => {
+            let percent = (timing.total_nanos - before.total_nanos) / before.total_nanos * 100.0;
+            format!("{percent:+.1}%")
+        }
+        _ => "-".into(),
+    }
+}
+
+fn update_content(
+    s: &mut String,
+    marker: &str,
+    timings: &BTreeMap<u16, Timings>,
+    baselines: Option<&BTreeMap<u16, Timings>>,
+    registry: &Registry,
+) -> Result<(), Error> {
+    let positions = locate_table(s, marker)?;
+    let primary_year = config::get().year;
+
+    // the primary year comes first and is always shown, other years once they have timings.
+    let mut years: Vec<u16> = timings
+        .iter()
+        .filter(|(year, timings)| **year != primary_year && !timings.data.is_empty())
+        .map(|(year, _)| *year)
+        .collect();
+    years.insert(0, primary_year);
+
+    let empty = Timings::default();
+    let tables: Vec<String> = years
+        .iter()
+        .map(|year| {
+            construct_table(
+                "##",
+                *year,
+                timings.get(year).unwrap_or(&empty),
+                baselines.and_then(|baselines| baselines.get(year)),
+                registry,
+            )
+        })
+        .collect();
+
+    let content = format!("{marker}\n{}\n{marker}", tables.join("\n\n"));
+    s.replace_range(positions.pos_start..positions.pos_end, &content);
     Ok(())
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
-    let path = "README.md";
+/// Update the benchmark tables in the readme, between the markers configured in `aoc.toml`.
+/// There is one table per year of `timings`, years other than the primary one are only listed
+/// if they have timings.
+/// If `baselines` is set, a column with the change of each day's total run time is added.
+/// Parts that a day does not have according to `registry` are shown as `n/a`.
+pub fn update(
+    timings: &BTreeMap<u16, Timings>,
+    baselines: Option<&BTreeMap<u16, Timings>>,
+    registry: &Registry,
+) -> Result<(), Error> {
+    let readme_config = &config::get().readme;
+    let path = &readme_config.path;
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
-    let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+    update_content(
+        &mut readme,
+        &readme_config.marker,
+        timings,
+        baselines,
+        registry,
+    )?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use std::collections::BTreeMap;
+
+    use super::{format_part, update_content};
+    use crate::{
+        day,
+        template::{
+            alloc_stats::AllocStats,
+            config,
+            stats::BenchStats,
+            timings::{Timing, Timings},
+            Registry, Solution,
+        },
+    };
+
+    static MARKER: &str = "<!--- benchmarking table --->";
+    static REGISTRY: Registry = Registry::new(&[]);
+
+    /// Timings of the primary year.
+    fn primary(timings: Timings) -> BTreeMap<u16, Timings> {
+        BTreeMap::from([(config::get().year, timings)])
+    }
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
                     day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
-                    total_nanos: 3e+10,
+                    parse: None,
+                    part_1: Some(BenchStats::single(10e6)),
+                    part_2: Some(BenchStats::single(20e6)),
+                    failed_parts: vec![],
+                    exceeded: None,
+                    alloc: BTreeMap::new(),
+                    total_nanos: 3e+7,
                 },
                 Timing {
                     day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
-                    total_nanos: 7e+10,
+                    parse: Some(BenchStats::single(5e6)),
+                    part_1: Some(BenchStats::single(30e6)),
+                    part_2: Some(BenchStats::single(40e6)),
+                    failed_parts: vec![],
+                    exceeded: None,
+                    alloc: BTreeMap::new(),
+                    total_nanos: 7e+7,
                 },
                 Timing {
                     day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: Some("50ms".into()),
-                    total_nanos: 9e+10,
+                    parse: None,
+                    part_1: Some(BenchStats::single(40e6)),
+                    part_2: Some(BenchStats::single(50e6)),
+                    failed_parts: vec![],
+                    exceeded: None,
+                    alloc: BTreeMap::new(),
+                    total_nanos: 9e+7,
                 },
             ],
         }
@@ -131,28 +309,63 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            MARKER,
+            &primary(get_mock_timings()),
+            None,
+            &REGISTRY,
+        )
+        .unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            MARKER,
+            &primary(get_mock_timings()),
+            None,
+            &REGISTRY,
+        )
+        .unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            MARKER,
+            &primary(get_mock_timings()),
+            None,
+            &REGISTRY,
+        )
+        .unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            MARKER,
+            &primary(get_mock_timings()),
+            None,
+            &REGISTRY,
+        )
+        .unwrap();
+        update_content(
+            &mut s,
+            MARKER,
+            &primary(get_mock_timings()),
+            None,
+            &REGISTRY,
+        )
+        .unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -160,18 +373,25 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            MARKER,
+            &primary(get_mock_timings()),
+            None,
+            &REGISTRY,
+        )
+        .unwrap();
         let expected = [
             "foo",
             "bar",
             "<!--- benchmarking table --->",
             "## Benchmarks",
             "",
-            "| Day | Part 1 | Part 2 |",
-            "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| Day | Parse | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/01.rs) | - | `10.0ms` | `20.0ms` |",
+            "| [Day 2](./src/bin/02.rs) | `5.0ms` | `30.0ms` | `40.0ms` |",
+            "| [Day 4](./src/bin/04.rs) | - | `40.0ms` | `50.0ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -180,4 +400,115 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_benched_parts() {
+        let stats = BenchStats {
+            samples: 100,
+            outliers: 0,
+            mean: 1_050_000.0,
+            median: 1_000_000.0,
+            min: 900_000.0,
+            max: 2_000_000.0,
+            std_dev: 50_000.0,
+            p95: 1_500_000.0,
+        };
+        assert_eq!(
+            format_part(Some(&stats)),
+            "`1.0ms` ± 50.0µs<br><sub>900.0µs … 2.0ms, p95 1.5ms, n=100</sub>"
+        );
+        assert_eq!(format_part(None), "-");
+    }
+
+    #[test]
+    fn format_delta_column() {
+        let mut s = format!("{}{}", MARKER, MARKER);
+        let baseline = Timings {
+            data: vec![Timing {
+                day: day!(1),
+                parse: None,
+                part_1: Some(BenchStats::single(10e6)),
+                part_2: Some(BenchStats::single(10e6)),
+                failed_parts: vec![],
+                exceeded: None,
+                alloc: BTreeMap::new(),
+                total_nanos: 2e+7,
+            }],
+        };
+        update_content(
+            &mut s,
+            MARKER,
+            &primary(get_mock_timings()),
+            Some(&primary(baseline)),
+            &REGISTRY,
+        )
+        .unwrap();
+        assert!(s.contains("| Day | Parse | Part 1 | Part 2 | Δ |"));
+        assert!(s.contains("| [Day 1](./src/bin/01.rs) | - | `10.0ms` | `20.0ms` | +50.0% |"));
+        assert!(s.contains("| [Day 2](./src/bin/02.rs) | `5.0ms` | `30.0ms` | `40.0ms` | - |"));
+    }
+
+    #[test]
+    fn format_alloc_columns() {
+        let mut s = format!("{}{}", MARKER, MARKER);
+        let mut timings = get_mock_timings();
+        timings.data[0].alloc = BTreeMap::from([
+            (
+                1,
+                AllocStats {
+                    peak_bytes: 2048,
+                    total_bytes: 4096,
+                    count: 3,
+                },
+            ),
+            (
+                2,
+                AllocStats {
+                    peak_bytes: 1024,
+                    total_bytes: 1024,
+                    count: 1,
+                },
+            ),
+        ]);
+        update_content(&mut s, MARKER, &primary(timings), None, &REGISTRY).unwrap();
+        assert!(s.contains("| Day | Parse | Part 1 | Part 2 | Peak memory | Allocations |"));
+        assert!(s.contains(
+            "| [Day 1](./src/bin/01.rs) | - | `10.0ms` | `20.0ms` | `2.0 KiB` | 4 (5.0 KiB) |"
+        ));
+        assert!(s.contains("| [Day 2](./src/bin/02.rs) | `5.0ms` | `30.0ms` | `40.0ms` | - | - |"));
+    }
+
+    #[test]
+    fn format_single_part_days() {
+        static SINGLE_PART: Registry =
+            Registry::new(&[Solution::__new(None, day!(4), &[1], |_| true)]);
+
+        let mut s = format!("{}{}", MARKER, MARKER);
+        let mut timings = get_mock_timings();
+        timings.data[2].part_2 = None;
+        update_content(&mut s, MARKER, &primary(timings), None, &SINGLE_PART).unwrap();
+        assert!(s.contains("| [Day 1](./src/bin/01.rs) | - | `10.0ms` | `20.0ms` |"));
+        assert!(s.contains("| [Day 4](./src/bin/04.rs) | - | `40.0ms` | n/a |"));
+    }
+
+    #[test]
+    fn format_several_years() {
+        let mut s = format!("{}{}", MARKER, MARKER);
+        let mut timings = primary(get_mock_timings());
+        timings.insert(2015, Timings::default());
+        timings.insert(
+            2016,
+            Timings {
+                data: get_mock_timings().data[..1].to_vec(),
+            },
+        );
+        update_content(&mut s, MARKER, &timings, None, &REGISTRY).unwrap();
+
+        assert_eq!(s.matches(MARKER).count(), 2);
+        assert!(s.starts_with(&format!("{MARKER}\n## Benchmarks\n")));
+        assert!(s.contains("**Total: 190.00ms**\n\n## 2016 Benchmarks\n"));
+        assert!(s.contains("| [Day 1](./src/bin/2016-01.rs) | - | `10.0ms` | `20.0ms` |"));
+        assert!(s.ends_with(&format!("**Total: 30.00ms**\n{MARKER}")));
+        assert!(!s.contains("2015"));
+    }
 }
@@ -0,0 +1,82 @@
+/// In-process access to every solution linked into the main binary.
+///
+/// `build.rs` collects all `src/bin/NN.rs` and `src/bin/YYYY-NN.rs` files into a module of the
+/// main binary, and each `solution!` invocation exposes a `SOLUTION` constant that is listed in a
+/// [`Registry`].
+use crate::template::{runner::RunOptions, Day, Puzzle};
+
+/// Parts of a day that does not declare a single part in `solution!`.
+pub const ALL_PARTS: &[u8] = &[1, 2];
+
+/// A single day's solution that can be run without spawning its binary.
+#[derive(Clone, Copy)]
+pub struct Solution {
+    /// The year of a `src/bin/YYYY-NN.rs` solution, `None` for the primary year.
+    pub year: Option<u16>,
+    pub day: Day,
+    /// The parts this day has, e.g. only `[1]` for a day declared with `solution!(25, 1)`.
+    pub parts: &'static [u8],
+    run: fn(&RunOptions) -> bool,
+}
+
+impl Solution {
+    // Not part of the public API, use the `solution!` macro instead.
+    #[doc(hidden)]
+    pub const fn __new(
+        year: Option<u16>,
+        day: Day,
+        parts: &'static [u8],
+        run: fn(&RunOptions) -> bool,
+    ) -> Self {
+        Self {
+            year,
+            day,
+            parts,
+            run,
+        }
+    }
+
+    pub fn puzzle(&self) -> Puzzle {
+        Puzzle::in_year(self.year, self.day)
+    }
+
+    /// Run all parts of this solution against the real input.
+    /// Returns `false` if the parse step or any part failed.
+    pub fn run(&self, options: &RunOptions) -> bool {
+        if let Some(part) = options.submit.filter(|p| !self.parts.contains(p)) {
+            eprintln!("Day {} has no part {part} to submit.", self.day);
+            return false;
+        }
+
+        (self.run)(options)
+    }
+}
+
+/// The set of solutions available to the current binary.
+pub struct Registry {
+    solutions: &'static [Solution],
+}
+
+impl Registry {
+    pub const fn new(solutions: &'static [Solution]) -> Self {
+        Self { solutions }
+    }
+
+    /// Returns the solution for `puzzle`, or `None` if it has not been scaffolded yet.
+    pub fn get(&self, puzzle: Puzzle) -> Option<&Solution> {
+        self.solutions.iter().find(|s| s.puzzle() == puzzle)
+    }
+
+    /// Returns the parts of `puzzle`, or [`ALL_PARTS`] if it has not been scaffolded yet.
+    pub fn parts(&self, puzzle: Puzzle) -> &'static [u8] {
+        self.get(puzzle).map_or(ALL_PARTS, |s| s.parts)
+    }
+
+    /// The years that have at least one solution, in order.
+    pub fn years(&self) -> Vec<u16> {
+        let mut years: Vec<u16> = self.solutions.iter().map(|s| s.puzzle().year).collect();
+        years.sort_unstable();
+        years.dedup();
+        years
+    }
+}
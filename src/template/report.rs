@@ -0,0 +1,340 @@
+/// Structured, machine-readable results emitted by the runner.
+///
+/// Every call to `run_part` produces one [`PartReport`]. If the environment variable
+/// [`REPORT_FILE_ENV`] points to a file, the report is appended to it as a single JSON line.
+/// When running in-process, reports can instead be captured with [`collect`].
+/// This lets `run_multi` gather answers and timings without scraping the human-readable output.
+use std::{
+    collections::HashMap,
+    env, fmt,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::Path,
+    str::FromStr,
+    sync::Mutex,
+};
+use tinyjson::JsonValue;
+
+use crate::template::{alloc_stats::AllocStats, stats::BenchStats, Day};
+
+/// Name of the environment variable that selects the report sidecar file.
+pub const REPORT_FILE_ENV: &str = "AOC_REPORT_FILE";
+
+/// Part number used for reports of the shared parse step.
+pub const PARSE_PART: u8 = 0;
+
+/// Outcome of running a single solution part.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    /// The part returned an answer.
+    Solved,
+    /// The part returned `None`, e.g. because it is not implemented yet.
+    Unsolved,
+    /// The part returned an error.
+    Failed,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Status::Solved => "solved",
+            Status::Unsolved => "unsolved",
+            Status::Failed => "failed",
+        })
+    }
+}
+
+impl FromStr for Status {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "solved" => Ok(Status::Solved),
+            "unsolved" => Ok(Status::Unsolved),
+            "failed" => Ok(Status::Failed),
+            _ => Err(format!("`{s}` is not a valid part status.")),
+        }
+    }
+}
+
+/// Answer and timing of a single solution part.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartReport {
+    pub day: Day,
+    /// The part number, or [`PARSE_PART`] for the parse step.
+    pub part: u8,
+    pub answer: Option<String>,
+    pub duration_nanos: f64,
+    pub samples: u128,
+    pub status: Status,
+    /// The error chain of a failed part.
+    pub error: Option<String>,
+    /// Benchmark statistics, if the part was benched.
+    pub stats: Option<BenchStats>,
+    /// Allocations of the first run, with the `alloc-stats` feature.
+    pub alloc: Option<AllocStats>,
+}
+
+impl PartReport {
+    /// A report of a single untimed run, for tests.
+    #[cfg(feature = "test_lib")]
+    pub fn test(day: Day, part: u8, answer: Option<&str>, status: Status) -> Self {
+        Self {
+            day,
+            part,
+            answer: answer.map(String::from),
+            duration_nanos: 0.0,
+            samples: 1,
+            status,
+            error: None,
+            stats: None,
+            alloc: None,
+        }
+    }
+}
+
+static COLLECTED: Mutex<Option<Vec<PartReport>>> = Mutex::new(None);
+
+/// Run `f` and return all reports emitted while it ran.
+pub fn collect<R>(f: impl FnOnce() -> R) -> (R, Vec<PartReport>) {
+    *COLLECTED.lock().unwrap() = Some(vec![]);
+    let result = f();
+    let reports = COLLECTED.lock().unwrap().take().unwrap_or_default();
+    (result, reports)
+}
+
+/// Record a report for an active [`collect`] call and append it to the sidecar file,
+/// if one was requested via [`REPORT_FILE_ENV`].
+pub fn emit(report: &PartReport) {
+    if let Some(collected) = COLLECTED.lock().unwrap().as_mut() {
+        collected.push(report.clone());
+    }
+
+    let Some(path) = env::var_os(REPORT_FILE_ENV) else {
+        return;
+    };
+
+    if let Err(e) = append_line(Path::new(&path), report) {
+        eprintln!("Failed to write report to {}: {e}", path.to_string_lossy());
+    }
+}
+
+fn append_line(path: &Path, report: &PartReport) -> io::Result<()> {
+    let line = JsonValue::from(report)
+        .stringify()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{line}")
+}
+
+/// Read all reports from a sidecar file. A missing file yields no reports.
+pub fn read_file(path: &Path) -> Result<Vec<PartReport>, String> {
+    match fs::read_to_string(path) {
+        Ok(s) => parse_lines(&s),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(vec![]),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Parse a JSON-lines document into reports, skipping blank lines.
+pub fn parse_lines(s: &str) -> Result<Vec<PartReport>, String> {
+    s.lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| {
+            let json = JsonValue::from_str(l).or(Err("report line is not valid JSON."))?;
+            PartReport::try_from(&json)
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartReport> for JsonValue {
+    fn from(value: &PartReport) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(value.part.into()));
+        map.insert(
+            "answer".into(),
+            match &value.answer {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert(
+            "duration_nanos".into(),
+            JsonValue::Number(value.duration_nanos),
+        );
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert("status".into(), JsonValue::String(value.status.to_string()));
+        map.insert(
+            "error".into(),
+            match &value.error {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert(
+            "stats".into(),
+            match &value.stats {
+                Some(x) => JsonValue::from(x),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert(
+            "alloc".into(),
+            match &value.alloc {
+                Some(x) => JsonValue::from(x),
+                None => JsonValue::Null,
+            },
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartReport {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected report to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected report.day to be a Day struct.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u8)
+            .ok_or("Expected report.part to be a number.")?;
+
+        let answer = json
+            .get("answer")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected report.answer to be null or string.")?;
+
+        let duration_nanos = json
+            .get("duration_nanos")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected report.duration_nanos to be a number.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let samples = json
+            .get("samples")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u128)
+            .ok_or("Expected report.samples to be a number.")?;
+
+        let status = json
+            .get("status")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected report.status to be a string.")?
+            .parse()?;
+
+        let error = json.get("error").and_then(|v| v.get::<String>()).cloned();
+
+        let stats = match json.get("stats") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(BenchStats::try_from(v)?),
+        };
+
+        let alloc = match json.get("alloc") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(AllocStats::try_from(v)?),
+        };
+
+        Ok(PartReport {
+            day,
+            part,
+            answer: answer.cloned(),
+            duration_nanos,
+            samples,
+            status,
+            error,
+            stats,
+            alloc,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_lines, PartReport, Status};
+    use crate::{
+        day,
+        template::{alloc_stats::AllocStats, stats::BenchStats},
+    };
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn roundtrips_reports() {
+        let report = PartReport {
+            day: day!(24),
+            part: 2,
+            answer: Some("i=3;\tnum bridges: 4 (1.0ms @ 10 samples)".into()),
+            duration_nanos: 74.13,
+            samples: 100_000,
+            status: Status::Solved,
+            error: None,
+            stats: Some(BenchStats {
+                samples: 100_000,
+                outliers: 3,
+                mean: 75.5,
+                median: 74.13,
+                min: 70.0,
+                max: 100.25,
+                std_dev: 2.5,
+                p95: 80.0,
+            }),
+            alloc: Some(AllocStats {
+                peak_bytes: 1024,
+                total_bytes: 4096,
+                count: 3,
+            }),
+        };
+        let line = JsonValue::from(&report).stringify().unwrap();
+        assert_eq!(parse_lines(&line).unwrap(), vec![report]);
+    }
+
+    #[test]
+    fn parses_unsolved_parts() {
+        let reports = parse_lines(
+            r#"{ "day": "01", "part": 1, "answer": null, "duration_nanos": 10, "samples": 1, "status": "unsolved" }
+
+"#,
+        )
+        .unwrap();
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].answer, None);
+        assert_eq!(reports[0].status, Status::Unsolved);
+    }
+
+    #[test]
+    fn parses_failed_parts() {
+        let reports = parse_lines(
+            r#"{ "day": "07", "part": 2, "answer": null, "duration_nanos": 10, "samples": 1, "status": "failed", "error": "bad input: line 3" }"#,
+        )
+        .unwrap();
+        assert_eq!(reports[0].status, Status::Failed);
+        assert_eq!(reports[0].error, Some("bad input: line 3".into()));
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_reports() {
+        parse_lines(r#"{ "day": "01", "part": 1 }"#).unwrap();
+    }
+}
@@ -1,257 +1,517 @@
-use std::{collections::HashSet, io};
-
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    env, fmt, fs,
+    io::{self, Read, Write},
+    panic,
+    process::{self, Child, Command, Stdio},
+    str::FromStr,
+    sync::{
+        mpsc::{self, RecvTimeoutError},
+        Mutex,
+    },
+    thread,
+    time::{Duration, Instant},
+};
 
-use super::{
-    all_days,
-    timings::{Timing, Timings},
+use crate::template::{
+    commands::run_day,
+    config,
+    dashboard::{Dashboard, REFRESH_INTERVAL},
+    report::{self, PartReport, REPORT_FILE_ENV},
+    runner::{BenchOptions, RunOptions},
+    Puzzle, Registry, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
-    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+use super::timings::{Timing, Timings};
 
-    let mut need_space = false;
+/// Exit code of a Rust process that panicked.
+const PANIC_EXIT_CODE: i32 = 101;
 
-    // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
-            }
-            need_space = true;
+/// How often a child process is checked against the [`RunLimits`].
+const LIMIT_POLL_INTERVAL: Duration = Duration::from_millis(5);
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+/// Limits of a single day, enforced by killing its child process.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RunLimits {
+    /// Wall-clock time a day may take, for all of its parts.
+    pub timeout: Option<Duration>,
+    /// Peak resident memory a day may use, in bytes. Only enforced on Linux.
+    pub memory: Option<u64>,
+}
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+impl RunLimits {
+    /// Parse `--timeout <seconds>` and `--max-memory <MiB>`.
+    /// Options that are not passed default to the `[limits]` section of `aoc.toml`.
+    pub fn parse(args: &mut pico_args::Arguments) -> Result<Self, pico_args::Error> {
+        let mut limits = config::get().limits;
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
-            }
-        });
+        if let Some(seconds) = args.opt_value_from_str("--timeout")? {
+            limits.timeout = Some(Duration::from_secs(seconds));
+        }
+        if let Some(mib) = args.opt_value_from_str::<_, u64>("--max-memory")? {
+            limits.memory = Some(mib * 1024 * 1024);
+        }
 
-    if is_timed {
-        let timings = Timings { data: timings };
-        let total_millis = timings.total_millis();
-        println!(
-            "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
-        );
-        Some(timings)
-    } else {
-        None
+        Ok(limits)
+    }
+
+    pub fn is_none(&self) -> bool {
+        self.timeout.is_none() && self.memory.is_none()
     }
 }
 
-#[derive(Debug)]
-pub enum Error {
-    BrokenPipe,
-    #[allow(dead_code)]
-    IO(io::Error),
+/// The limit a day was stopped at.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LimitExceeded {
+    Timeout,
+    Memory,
 }
 
-impl From<std::io::Error> for Error {
-    fn from(e: std::io::Error) -> Self {
-        Error::IO(e)
+impl LimitExceeded {
+    /// Name of the limit in `timings.json`.
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::Timeout => "timeout",
+            Self::Memory => "memory",
+        }
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+impl fmt::Display for LimitExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Timeout => "timed out",
+            Self::Memory => "OOM",
+        })
+    }
 }
 
-/// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
-pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
-    use std::{
-        io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
-        thread,
-    };
+impl FromStr for LimitExceeded {
+    type Err = String;
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "timeout" => Ok(Self::Timeout),
+            "memory" => Ok(Self::Memory),
+            _ => Err(format!("`{s}` is not a valid limit.")),
         }
+    }
+}
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+/// Where the output of the days goes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Output {
+    #[default]
+    Stdout,
+    /// Keeps stdout free, e.g. for a report. Days always run in child processes then, so that
+    /// their output can be redirected.
+    Stderr,
+    /// Shows the progress of all days on a [`Dashboard`] instead of their output. Days always
+    /// run in child processes.
+    Dashboard,
+}
 
-        if is_release {
-            args.push("--release");
+impl Output {
+    pub fn writer(self) -> Box<dyn Write> {
+        match self {
+            Self::Stdout => Box::new(io::stdout()),
+            Self::Stderr => Box::new(io::stderr()),
+            Self::Dashboard => Box::new(io::sink()),
         }
+    }
+}
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
-        }
+/// Outcome of running multiple days, possibly of several years.
+pub struct RunSummary {
+    /// Timings of the days that ran, by year.
+    pub timings: BTreeMap<u16, Timings>,
+    /// Puzzles where the parse step or a part failed, the solution panicked or was stopped.
+    pub failed: Vec<Puzzle>,
+    /// Puzzles that panicked or were stopped at a limit, with the reason.
+    pub aborted: Vec<(Puzzle, String)>,
+    /// Reports of all parts that ran, by year and in day order.
+    pub reports: BTreeMap<u16, Vec<PartReport>>,
+}
 
-        // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+enum DayResult {
+    Passed,
+    Failed,
+    Panicked,
+    Exceeded(LimitExceeded),
+}
 
-        let mut cmd = Command::new("cargo")
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
+impl DayResult {
+    /// Why the day failed, `None` if it passed.
+    fn failure(&self) -> Option<String> {
+        match self {
+            Self::Passed => None,
+            Self::Failed => Some("failed".into()),
+            Self::Panicked => Some("panicked".into()),
+            Self::Exceeded(limit) => Some(limit.to_string()),
+        }
+    }
+}
 
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
+/// Progress of a day that runs in a child process.
+enum DayEvent {
+    Started(Puzzle),
+    Finished(Puzzle, DayOutput),
+}
 
-        let mut output = vec![];
+/// Buffered output of a day that ran in a child process.
+struct DayOutput {
+    result: DayResult,
+    stdout: Vec<u8>,
+    stderr: Vec<u8>,
+    reports: Vec<PartReport>,
+}
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
-        });
+/// Run the given puzzles and print their output to `output`, in order of year and day.
+/// Days are benched if `bench` is set. The year is only shown if puzzles of several years run.
+///
+/// With `jobs <= 1`, no `limits` and output to stdout, days run one after another inside the
+/// current process.
+/// Otherwise up to `jobs` days run concurrently in child processes, and their output is buffered
+/// until all previous days have been printed. A child that exceeds one of the `limits` is killed,
+/// the parts it finished are kept.
+pub fn run_multi(
+    registry: &Registry,
+    to_run: &HashSet<Puzzle>,
+    bench: Option<BenchOptions>,
+    jobs: usize,
+    limits: RunLimits,
+    output: Output,
+) -> RunSummary {
+    let mut puzzles: Vec<Puzzle> = to_run.iter().copied().collect();
+    puzzles.sort_unstable();
+
+    let with_year = puzzles.iter().any(|p| p.year != puzzles[0].year);
+
+    let mut timings: BTreeMap<u16, Timings> = BTreeMap::new();
+    let mut failed: Vec<Puzzle> = vec![];
+    let mut exceeded: Vec<(Puzzle, LimitExceeded)> = vec![];
+    let mut aborted: Vec<(Puzzle, String)> = vec![];
+    let mut all_reports: BTreeMap<u16, Vec<PartReport>> = BTreeMap::new();
+
+    if limits.memory.is_some() && !cfg!(target_os = "linux") {
+        eprintln!("Warning: the memory limit is only enforced on Linux.");
+    }
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+    let mut finish_day = |puzzle: Puzzle, result: DayResult, reports: &[PartReport]| {
+        let mut timing = Timing::from_reports(puzzle.day, reports);
+
+        match result {
+            DayResult::Passed => {}
+            DayResult::Failed => failed.push(puzzle),
+            DayResult::Panicked => {
+                eprintln!("{} panicked.", puzzle.label(with_year));
+                failed.push(puzzle);
+                aborted.push((puzzle, "panicked".into()));
+            }
+            DayResult::Exceeded(limit) => {
+                let label = puzzle.label(with_year);
+                match (limit, limits.timeout, limits.memory) {
+                    (LimitExceeded::Timeout, Some(timeout), _) => {
+                        eprintln!("{label} timed out after {}s.", timeout.as_secs());
+                    }
+                    (LimitExceeded::Memory, _, Some(memory)) => eprintln!(
+                        "{label} exceeded the memory limit of {} MiB.",
+                        memory / 1024 / 1024
+                    ),
+                    _ => eprintln!("{label} {limit}."),
+                }
+                timing.exceeded = Some(limit);
+                failed.push(puzzle);
+                exceeded.push((puzzle, limit));
+                aborted.push((puzzle, limit.to_string()));
+            }
         }
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        timings.entry(puzzle.year).or_default().data.push(timing);
+        all_reports
+            .entry(puzzle.year)
+            .or_default()
+            .extend_from_slice(reports);
+    };
 
-        Ok(output)
-    }
+    let mut out = output.writer();
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
+    if jobs <= 1 && limits.is_none() && output == Output::Stdout {
+        let options = RunOptions {
+            bench,
+            ..RunOptions::default()
         };
 
-        output
+        for (i, &puzzle) in puzzles.iter().enumerate() {
+            print_header(&mut out, puzzle, with_year, i > 0);
+
+            let Some(solution) = registry.get(puzzle) else {
+                writeln!(out, "Not solved.").unwrap();
+                continue;
+            };
+
+            let (result, reports) =
+                report::collect(|| panic::catch_unwind(|| solution.run(&options)));
+
+            let result = match result {
+                Ok(true) => DayResult::Passed,
+                Ok(false) => DayResult::Failed,
+                Err(_) => DayResult::Panicked,
+            };
+
+            finish_day(puzzle, result, &reports);
+        }
+    } else {
+        let solved: Vec<Puzzle> = puzzles
             .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
+            .copied()
+            .filter(|puzzle| registry.get(*puzzle).is_some())
+            .collect();
+
+        let queue = Mutex::new(solved.iter().copied());
+
+        thread::scope(|scope| {
+            let (sender, receiver) = mpsc::channel();
+
+            for _ in 0..jobs.max(1).min(solved.len()) {
+                let queue = &queue;
+                let sender = sender.clone();
+                scope.spawn(move || loop {
+                    let Some(puzzle) = queue.lock().unwrap().next() else {
+                        break;
+                    };
+                    if sender.send(DayEvent::Started(puzzle)).is_err() {
+                        break;
+                    }
+                    let day_output = run_child(puzzle, bench, limits);
+                    if sender.send(DayEvent::Finished(puzzle, day_output)).is_err() {
+                        break;
+                    }
+                });
+            }
+
+            drop(sender);
+
+            let mut finished: HashMap<Puzzle, DayOutput> = HashMap::new();
+
+            // the dashboard shows days as they finish, their output is only used for the summary.
+            if output == Output::Dashboard {
+                let mut dashboard = Dashboard::new(&puzzles, registry, with_year);
+                dashboard.draw();
+
+                while finished.len() < solved.len() {
+                    match receiver.recv_timeout(REFRESH_INTERVAL) {
+                        Ok(DayEvent::Started(puzzle)) => dashboard.start(puzzle),
+                        Ok(DayEvent::Finished(puzzle, day_output)) => {
+                            let failure = day_output.result.failure();
+                            dashboard.finish(puzzle, failure, &day_output.reports);
+                            finished.insert(puzzle, day_output);
+                        }
+                        Err(RecvTimeoutError::Timeout) => {}
+                        Err(RecvTimeoutError::Disconnected) => panic!("worker thread panicked."),
+                    }
+                    dashboard.draw();
                 }
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
+                dashboard.close();
+            }
 
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
+            for (i, &puzzle) in puzzles.iter().enumerate() {
+                print_header(&mut out, puzzle, with_year, i > 0);
+
+                if registry.get(puzzle).is_none() {
+                    writeln!(out, "Not solved.").unwrap();
+                    continue;
                 }
 
-                timings.total_nanos += nanos;
-            });
+                let day_output = loop {
+                    if let Some(day_output) = finished.remove(&puzzle) {
+                        break day_output;
+                    }
+                    if let DayEvent::Finished(done, day_output) =
+                        receiver.recv().expect("worker thread panicked.")
+                    {
+                        finished.insert(done, day_output);
+                    }
+                };
+
+                out.write_all(&day_output.stdout).unwrap();
+                out.flush().unwrap();
+                io::stderr().write_all(&day_output.stderr).unwrap();
 
-        timings
+                finish_day(puzzle, day_output.result, &day_output.reports);
+            }
+        });
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
+    if bench.is_some() {
+        let total_millis: f64 = timings.values().map(Timings::total_millis).sum();
+        writeln!(
+            out,
+            "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
+        )
+        .unwrap();
     }
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+    if !failed.is_empty() {
+        let labels: Vec<String> = failed
+            .iter()
+            .map(|p| match exceeded.iter().find(|(e, _)| e == p) {
+                Some((_, limit)) => format!("{} ({limit})", p.label(with_year)),
+                None => p.label(with_year),
+            })
+            .collect();
+        eprintln!("\n{ANSI_BOLD}Failed:{ANSI_RESET} {}", labels.join(", "));
     }
 
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
+    RunSummary {
+        timings,
+        failed,
+        aborted,
+        reports: all_reports,
+    }
+}
+
+fn print_header(out: &mut dyn Write, puzzle: Puzzle, with_year: bool, need_space: bool) {
+    if need_space {
+        writeln!(out).unwrap();
     }
 
-    #[cfg(feature = "test_lib")]
-    mod tests {
-        use super::parse_exec_time;
-
-        use crate::day;
-
-        #[test]
-        fn parses_execution_times() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+    let label = puzzle.label(with_year);
+    writeln!(out, "{ANSI_BOLD}{label}{ANSI_RESET}").unwrap();
+    writeln!(out, "{}", "-".repeat(label.chars().count())).unwrap();
+}
+
+/// Run a single day by re-executing the current binary with the hidden [`run_day::COMMAND`].
+/// Reports are collected through a sidecar file, so they survive if the child is killed.
+fn run_child(puzzle: Puzzle, bench: Option<BenchOptions>, limits: RunLimits) -> DayOutput {
+    let report_path = env::temp_dir().join(format!("aoc-report-{}-{puzzle}.jsonl", process::id()));
+    let _ = fs::remove_file(&report_path);
+
+    let output = env::current_exe().and_then(|exe| {
+        let mut cmd = Command::new(exe);
+        cmd.args([
+            run_day::COMMAND,
+            &puzzle.day.to_string(),
+            "--year",
+            &puzzle.year.to_string(),
+        ])
+        .env(REPORT_FILE_ENV, &report_path);
+
+        if let Some(bench) = bench {
+            cmd.arg("--time").args(bench.to_args());
         }
 
-        #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+        cmd.stdout(Stdio::piped()).stderr(Stdio::piped()).spawn()
+    });
+
+    let mut day_output = match output.and_then(|child| wait_with_limits(child, limits)) {
+        Ok(output) => output,
+        Err(e) => DayOutput {
+            result: DayResult::Failed,
+            stdout: vec![],
+            stderr: format!("Failed to run day {}: {e}\n", puzzle.day).into_bytes(),
+            reports: vec![],
+        },
+    };
+
+    match report::read_file(&report_path) {
+        Ok(reports) => day_output.reports = reports,
+        Err(e) => day_output
+            .stderr
+            .extend(format!("Failed to read reports of day {}: {e}\n", puzzle.day).into_bytes()),
+    }
+
+    let _ = fs::remove_file(&report_path);
+
+    day_output
+}
+
+/// Wait for `child` to exit, or kill it once it exceeds one of the `limits`.
+/// Returns its output without reports.
+fn wait_with_limits(mut child: Child, limits: RunLimits) -> io::Result<DayOutput> {
+    // the pipes are drained while waiting, so that a child with a lot of output doesn't block.
+    fn drain(pipe: Option<impl Read + Send + 'static>) -> thread::JoinHandle<Vec<u8>> {
+        thread::spawn(move || {
+            let mut buffer = vec![];
+            if let Some(mut pipe) = pipe {
+                let _ = pipe.read_to_end(&mut buffer);
+            }
+            buffer
+        })
+    }
+    let stdout = drain(child.stdout.take());
+    let stderr = drain(child.stderr.take());
+
+    let start = Instant::now();
+    let exceeded = loop {
+        if child.try_wait()?.is_some() {
+            break None;
+        }
+        if limits
+            .timeout
+            .is_some_and(|timeout| start.elapsed() > timeout)
+        {
+            break Some(LimitExceeded::Timeout);
+        }
+        if limits
+            .memory
+            .is_some_and(|memory| peak_memory(child.id()).is_some_and(|peak| peak > memory))
+        {
+            break Some(LimitExceeded::Memory);
         }
+        thread::sleep(LIMIT_POLL_INTERVAL);
+    };
+
+    if exceeded.is_some() {
+        let _ = child.kill();
+    }
+    let status = child.wait()?;
+
+    Ok(DayOutput {
+        result: match (exceeded, status.code()) {
+            (Some(limit), _) => DayResult::Exceeded(limit),
+            (None, Some(0)) => DayResult::Passed,
+            (None, Some(PANIC_EXIT_CODE)) => DayResult::Panicked,
+            _ => DayResult::Failed,
+        },
+        stdout: stdout.join().unwrap_or_default(),
+        stderr: stderr.join().unwrap_or_default(),
+        reports: vec![],
+    })
+}
+
+/// Peak resident memory of the process `pid` in bytes, `None` if it can't be determined.
+fn peak_memory(pid: u32) -> Option<u64> {
+    let status = fs::read_to_string(format!("/proc/{pid}/status")).ok()?;
+    parse_peak_memory(&status)
+}
+
+/// Parse the high water mark of the resident set from the contents of `/proc/<pid>/status`.
+fn parse_peak_memory(status: &str) -> Option<u64> {
+    let line = status.lines().find(|line| line.starts_with("VmHWM:"))?;
+    let kib: u64 = line.split_whitespace().nth(1)?.parse().ok()?;
+    Some(kib * 1024)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_peak_memory, LimitExceeded};
+
+    #[test]
+    fn parses_peak_memory() {
+        let status = "Name:\t01\nVmPeak:\t  10000 kB\nVmHWM:\t    2048 kB\nVmRSS:\t    1024 kB\n";
+        assert_eq!(parse_peak_memory(status), Some(2048 * 1024));
+        assert_eq!(parse_peak_memory("Name:\t01\n"), None);
+    }
 
-        #[test]
-        fn parses_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
+    #[test]
+    fn round_trips_exceeded_limits() {
+        for limit in [LimitExceeded::Timeout, LimitExceeded::Memory] {
+            assert_eq!(limit.key().parse(), Ok(limit));
         }
+        assert!("forever".parse::<LimitExceeded>().is_err());
     }
 }
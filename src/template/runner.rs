@@ -1,69 +1,492 @@
 /// Encapsulates code that interacts with solution functions.
 use std::fmt::Display;
 use std::hint::black_box;
-use std::io::{stdout, Write};
-use std::process::Output;
+use std::io::{stdin, stdout, IsTerminal, Read, Write};
+use std::path::PathBuf;
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{fs, process};
 
+pub use crate::template::report::Status;
+
+use crate::template::alloc_stats::{self, format_bytes, AllocStats};
+use crate::template::answers;
+use crate::template::config;
+use crate::template::params::Param;
+use crate::template::report::{self, PartReport, PARSE_PART};
+use crate::template::stats::{format_nanos, BenchStats};
+use crate::template::submissions::{Attempt, SubmissionLog};
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{aoc_client::AocClient, unix_now, Puzzle, ANSI_ITALIC, ANSI_RESET};
+
+/// Lower bound of samples collected when benching with a time budget.
+const MIN_BUDGET_SAMPLES: usize = 10;
+/// Upper bound of samples collected when benching with a time budget.
+const MAX_BUDGET_SAMPLES: usize = 1_000_000;
+
+/// Determines how many samples are collected when benching a part.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BenchLimit {
+    /// Sample until the budget is spent, collecting at least 10 samples.
+    Budget(Duration),
+    /// Collect exactly this many samples.
+    Samples(usize),
+}
+
+/// Options that control how a part is benched.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BenchOptions {
+    pub limit: BenchLimit,
+    /// Number of untimed runs before sampling starts.
+    pub warmup: usize,
+    /// Discard samples outside of Tukey's fences before computing statistics.
+    pub reject_outliers: bool,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        Self {
+            limit: BenchLimit::Budget(Duration::from_secs(1)),
+            warmup: 1,
+            reject_outliers: false,
+        }
+    }
+}
+
+impl BenchOptions {
+    /// Parse `--samples <n>`, `--budget <ms>`, `--warmup <n>` and `--reject-outliers`.
+    /// Options that are not passed default to the `[bench]` section of `aoc.toml`.
+    pub fn parse(args: &mut pico_args::Arguments) -> Result<Self, pico_args::Error> {
+        Self::parse_with_defaults(args, config::get().bench)
+    }
+
+    fn parse_with_defaults(
+        args: &mut pico_args::Arguments,
+        defaults: Self,
+    ) -> Result<Self, pico_args::Error> {
+        let mut options = defaults;
+
+        if let Some(samples) = args.opt_value_from_str::<_, usize>("--samples")? {
+            options.limit = BenchLimit::Samples(samples.max(1));
+        } else if let Some(millis) = args.opt_value_from_str("--budget")? {
+            options.limit = BenchLimit::Budget(Duration::from_millis(millis));
+        }
+
+        if let Some(warmup) = args.opt_value_from_str("--warmup")? {
+            options.warmup = warmup;
+        }
+
+        if args.contains("--reject-outliers") {
+            options.reject_outliers = true;
+        }
+
+        Ok(options)
+    }
+
+    /// The inverse of [`BenchOptions::parse`], used to pass the options on to a child process.
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = match self.limit {
+            BenchLimit::Samples(samples) => vec!["--samples".into(), samples.to_string()],
+            BenchLimit::Budget(budget) => vec!["--budget".into(), budget.as_millis().to_string()],
+        };
+
+        args.extend(["--warmup".into(), self.warmup.to_string()]);
+
+        if self.reject_outliers {
+            args.push("--reject-outliers".into());
+        }
+
+        args
+    }
+}
+
+/// Where a solution reads its input from.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum InputSource {
+    /// The puzzle input, `data/inputs/NN.txt`.
+    #[default]
+    Puzzle,
+    /// An example, `data/examples/NN.txt` or `data/examples/NN-<n>.txt` if a number is given.
+    Example(Option<u8>),
+    /// An arbitrary file.
+    File(PathBuf),
+    /// Everything that is written to stdin.
+    Stdin,
+}
+
+impl InputSource {
+    /// Parse `--example [<n>]`, `--input <path>` or `--stdin`. Defaults to the puzzle input.
+    pub fn parse(args: &mut pico_args::Arguments) -> Result<Self, pico_args::Error> {
+        // `--example` takes an optional number, peek at a copy so a following flag is kept.
+        let example = match args.clone().opt_value_from_str::<_, u8>("--example") {
+            Ok(Some(_)) => Some(args.opt_value_from_str("--example")?),
+            _ => args.contains("--example").then_some(None),
+        };
+        let file = args.opt_value_from_str("--input")?;
+        let stdin = args.contains("--stdin");
+
+        match (example, file, stdin) {
+            (None, None, false) => Ok(Self::Puzzle),
+            (Some(n), None, false) => Ok(Self::Example(n)),
+            (None, Some(path), false) => Ok(Self::File(path)),
+            (None, None, true) => Ok(Self::Stdin),
+            _ => Err(pico_args::Error::ArgumentParsingFailed {
+                cause: "only one of `--example`, `--input` and `--stdin` can be passed".into(),
+            }),
+        }
+    }
+
+    /// Convert the source back to arguments that [`InputSource::parse`] accepts.
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            Self::Puzzle => vec![],
+            Self::Example(None) => vec!["--example".into()],
+            Self::Example(Some(n)) => vec!["--example".into(), n.to_string()],
+            Self::File(path) => vec!["--input".into(), path.to_string_lossy().into_owned()],
+            Self::Stdin => vec!["--stdin".into()],
+        }
+    }
+
+    /// Read the input of `puzzle` from this source.
+    pub fn read(&self, puzzle: Puzzle) -> Result<String, String> {
+        let path = match self {
+            Self::Puzzle => puzzle.paths().input(puzzle.day),
+            Self::Example(n) => puzzle.paths().example(puzzle.day, *n),
+            Self::File(path) => path.clone(),
+            Self::Stdin => {
+                let mut input = String::new();
+                stdin()
+                    .read_to_string(&mut input)
+                    .map_err(|e| format!("could not read input from stdin: {e}"))?;
+                return Ok(input);
+            }
+        };
+
+        fs::read_to_string(&path)
+            .map_err(|e| format!("could not open input file `{}`: {e}", path.display()))
+    }
+}
+
+/// Options that control how the parts of a solution are run.
+#[derive(Clone, Debug, Default)]
+pub struct RunOptions {
+    /// The input the parts are run against.
+    pub input: InputSource,
+    /// Overrides of the solution's parameters, from `--param key=value`.
+    pub params: Vec<Param>,
+    /// Bench each part instead of running it once.
+    pub bench: Option<BenchOptions>,
+    /// Submit the answer of this part after running it.
+    pub submit: Option<u8>,
+    /// Store the answers of all solved parts as known answers.
+    pub accept: bool,
+}
+
+impl RunOptions {
+    /// Parse the arguments passed to a solution binary, e.g. `--time` or `--submit 1`.
+    pub fn from_args() -> Self {
+        let mut args = pico_args::Arguments::from_env();
+
+        let parsed = (|| {
+            let input = InputSource::parse(&mut args)?;
+            let params = args.values_from_str("--param")?;
+            let submit = args.opt_value_from_str("--submit")?;
+            let accept = args.contains("--accept");
+            let bench = if args.contains("--time") {
+                Some(BenchOptions::parse(&mut args)?)
+            } else {
+                None
+            };
+
+            if input != InputSource::Puzzle && (submit.is_some() || accept) {
+                return Err(pico_args::Error::ArgumentParsingFailed {
+                    cause: "`--submit` and `--accept` only work with the puzzle input".into(),
+                });
+            }
+
+            Ok::<_, pico_args::Error>(Self {
+                input,
+                params,
+                bench,
+                submit,
+                accept,
+            })
+        })();
+
+        match parsed {
+            Ok(options) => options,
+            Err(e) => {
+                eprintln!(
+                    "{e}. Format: cargo solve 1 [--submit 1] [--accept] [--time [--samples 100]] [--example [2] | --input <path> | --stdin] [--param key=value]"
+                );
+                process::exit(1);
+            }
+        }
+    }
+}
+
+/// The result of a solution part, independent of the type the part returns.
+#[derive(Debug)]
+pub enum Outcome {
+    /// The part produced an answer.
+    Solved(String),
+    /// The part returned `None`, e.g. because it is not implemented yet.
+    Unsolved,
+    /// The part returned an error.
+    Failed(anyhow::Error),
+}
+
+/// Return types that a solution part may have. `M` is a marker that keeps the implementations
+/// for `Option<T>`, `Result<T, E>` and `Result<Option<T>, E>` from overlapping.
+pub trait PartOutput<M> {
+    fn into_outcome(self) -> Outcome;
+}
+
+#[doc(hidden)]
+pub struct OptionMarker;
+#[doc(hidden)]
+pub struct ResultMarker;
+#[doc(hidden)]
+pub struct ResultOptionMarker;
+
+impl<T: Display> PartOutput<OptionMarker> for Option<T> {
+    fn into_outcome(self) -> Outcome {
+        match self {
+            Some(answer) => Outcome::Solved(answer.to_string()),
+            None => Outcome::Unsolved,
+        }
+    }
+}
+
+impl<T: Display, E: Into<anyhow::Error>> PartOutput<ResultMarker> for Result<T, E> {
+    fn into_outcome(self) -> Outcome {
+        match self {
+            Ok(answer) => Outcome::Solved(answer.to_string()),
+            Err(e) => Outcome::Failed(e.into()),
+        }
+    }
+}
+
+impl<T: Display, E: Into<anyhow::Error>> PartOutput<ResultOptionMarker> for Result<Option<T>, E> {
+    fn into_outcome(self) -> Outcome {
+        match self {
+            Ok(answer) => answer.into_outcome(),
+            Err(e) => Outcome::Failed(e.into()),
+        }
+    }
+}
+
+/// Run the shared parse step of a solution and return its output, which is passed to each part.
+pub fn run_parse<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    puzzle: Puzzle,
+    options: &RunOptions,
+) -> T {
+    let (result, measurement) = run_timed(func, input, options.bench.as_ref(), |_| {
+        print_progress("Parse:");
+        true
+    });
+
+    clear_progress();
+    println!("Parse:{}", format_timing(&measurement));
+
+    emit_report(
+        puzzle,
+        PARSE_PART,
+        &Outcome::Solved(String::new()),
+        &measurement,
+    );
+
+    result
+}
+
+/// Read the input selected by `options`.
+/// Returns `None` and prints the error if the input can't be read.
+pub fn read_input(puzzle: Puzzle, options: &RunOptions) -> Option<String> {
+    match options.input.read(puzzle) {
+        Ok(input) => Some(input),
+        Err(e) => {
+            eprintln!("{ANSI_BOLD}Error:{ANSI_RESET} {e}");
+            None
+        }
+    }
+}
+
+/// Like [`run_parse`], for parse functions that return a [`Result`].
+/// Returns `None` and reports the parse step as failed if parsing returns an error.
+pub fn run_try_parse<I: Clone, T, E: Into<anyhow::Error>>(
+    func: impl Fn(I) -> Result<T, E>,
+    input: I,
+    puzzle: Puzzle,
+    options: &RunOptions,
+) -> Option<T> {
+    let (result, measurement) = run_timed(func, input, options.bench.as_ref(), |result| {
+        print_progress("Parse:");
+        result.is_ok()
+    });
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+    clear_progress();
+
+    match result {
+        Ok(parsed) => {
+            println!("Parse:{}", format_timing(&measurement));
+            emit_report(
+                puzzle,
+                PARSE_PART,
+                &Outcome::Solved(String::new()),
+                &measurement,
+            );
+            Some(parsed)
+        }
+        Err(e) => {
+            let outcome = Outcome::Failed(e.into());
+            print_result(&outcome, "Parse", &format_timing(&measurement));
+            emit_report(puzzle, PARSE_PART, &outcome, &measurement);
+            None
+        }
+    }
+}
+
+/// Run a solution part, print its answer and timing, and return its [`Status`].
+pub fn run_part<I: Clone, M, R: PartOutput<M>>(
+    func: impl Fn(I) -> R,
+    input: I,
+    puzzle: Puzzle,
+    part: u8,
+    options: &RunOptions,
+) -> Status {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (result, measurement) = run_timed(
+        |i| func(i).into_outcome(),
+        input,
+        options.bench.as_ref(),
+        |outcome| {
+            print_result(outcome, &part_str, "");
+            // only bench parts that produced an answer.
+            matches!(outcome, Outcome::Solved(_))
+        },
+    );
+
+    print_result(&result, &part_str, &format_timing(&measurement));
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    let status = emit_report(puzzle, part, &result, &measurement);
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    if let Outcome::Solved(answer) = result {
+        if options.accept {
+            accept_answer(puzzle, part, &answer);
+        }
+        submit_result(answer, puzzle, part, options);
     }
+
+    status
+}
+
+/// Time and allocations of a part.
+struct Measurement {
+    base_time: Duration,
+    /// Statistics of all samples, if the part was benched.
+    stats: Option<BenchStats>,
+    /// Allocations of the first run, with the `alloc-stats` feature.
+    alloc: Option<AllocStats>,
 }
 
-/// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
-///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+fn emit_report(puzzle: Puzzle, part: u8, outcome: &Outcome, measurement: &Measurement) -> Status {
+    let Measurement {
+        base_time,
+        stats,
+        alloc,
+    } = *measurement;
+
+    let (status, answer, error) = match outcome {
+        Outcome::Solved(_) if part == PARSE_PART => (Status::Solved, None, None),
+        Outcome::Solved(answer) => (Status::Solved, Some(answer.clone()), None),
+        Outcome::Unsolved => (Status::Unsolved, None, None),
+        Outcome::Failed(e) => (Status::Failed, None, Some(format!("{e:#}"))),
+    };
+
+    report::emit(&PartReport {
+        day: puzzle.day,
+        part,
+        answer,
+        #[allow(clippy::cast_precision_loss)]
+        duration_nanos: stats.map_or(base_time.as_nanos() as f64, |s| s.median),
+        samples: stats.map_or(1, |s| s.samples),
+        status,
+        error,
+        stats,
+        alloc,
+    });
+
+    status
+}
+
+/// Run a solution part. The behavior differs depending on whether benching was requested:
+///  1. by default, the function is executed once.
+///  2. with `--time`, the function is benched according to the [`BenchOptions`],
+///     unless `hook` returns `false` for the result of the first run.
+///
+/// Allocations are counted for the first run only.
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
-    hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+    bench_options: Option<&BenchOptions>,
+    hook: impl Fn(&T) -> bool,
+) -> (T, Measurement) {
     let timer = Instant::now();
-    let result = {
+    let (result, alloc) = {
         let input = input.clone();
 
         #[cfg(feature = "dhat-heap")]
         let _profiler = dhat::Profiler::new_heap();
 
-        func(input)
+        alloc_stats::measure(|| func(input))
     };
     let base_time = timer.elapsed();
 
-    hook(&result);
-
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    let stats = if hook(&result) {
+        bench_options.and_then(|options| bench(func, input, options))
     } else {
-        (base_time, 1)
+        None
     };
 
-    (result, run.0, run.1)
+    (
+        result,
+        Measurement {
+            base_time,
+            stats,
+            alloc,
+        },
+    )
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
-
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+fn bench<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    options: &BenchOptions,
+) -> Option<BenchStats> {
+    print_progress(&format!(" > {ANSI_ITALIC}benching{ANSI_RESET}"));
 
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    for _ in 0..options.warmup {
+        black_box(func(black_box(input.clone())));
+    }
 
     let mut timers: Vec<Duration> = vec![];
+    let started = Instant::now();
+
+    loop {
+        let done = match options.limit {
+            BenchLimit::Samples(samples) => timers.len() >= samples,
+            BenchLimit::Budget(budget) => {
+                timers.len() >= MAX_BUDGET_SAMPLES
+                    || (timers.len() >= MIN_BUDGET_SAMPLES && started.elapsed() >= budget)
+            }
+        };
+
+        if done {
+            break;
+        }
 
-    for _ in 0..bench_iterations {
         // need a clone here to make the borrow checker happy.
         let cloned = input.clone();
         let timer = Instant::now();
@@ -71,99 +494,244 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
+    BenchStats::from_samples(&timers, options.reject_outliers)
+}
+
+fn format_timing(measurement: &Measurement) -> String {
+    let alloc = measurement.alloc.map_or(String::new(), |alloc| {
+        format!(
+            ", peak {}, {} in {} allocations",
+            format_bytes(alloc.peak_bytes),
+            format_bytes(alloc.total_bytes),
+            alloc.count
+        )
+    });
+
+    let Some(stats) = measurement.stats else {
+        return format!(" ({:.1?}{alloc})", measurement.base_time);
+    };
+
+    let outliers = if stats.outliers > 0 {
+        format!(", {} outliers rejected", stats.outliers)
+    } else {
+        String::new()
+    };
+
+    format!(
+        " ({} ± {} [{} … {}], p95 {} @ {} samples{outliers}{alloc})",
+        format_nanos(stats.median),
+        format_nanos(stats.std_dev),
+        format_nanos(stats.min),
+        format_nanos(stats.max),
+        format_nanos(stats.p95),
+        stats.samples,
     )
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+/// Print an intermediate result that is overwritten once the part finished, e.g. while benching.
+/// Only done if stdout is a terminal: piped output, like that of days running in child
+/// processes, gets the final lines only.
+fn print_progress(s: &str) {
+    let mut stdout = stdout();
+    if stdout.is_terminal() {
+        print!("{s}");
+        let _ = stdout.flush();
+    }
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
-    } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+/// Return to the start of the line of [`print_progress`].
+fn clear_progress() {
+    if stdout().is_terminal() {
+        print!("\r");
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn print_result(outcome: &Outcome, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
-    match result {
-        Some(result) => {
-            if result.to_string().contains('\n') {
+    match outcome {
+        Outcome::Solved(result) => {
+            if result.contains('\n') {
                 let str = format!("{part}: ▼ {duration_str}");
                 if is_intermediate_result {
-                    print!("{str}");
+                    print_progress(&str);
                 } else {
-                    print!("\r");
+                    clear_progress();
                     println!("{str}");
                     println!("{result}");
                 }
             } else {
                 let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
                 if is_intermediate_result {
-                    print!("{str}");
+                    print_progress(&str);
                 } else {
-                    print!("\r");
+                    clear_progress();
                     println!("{str}");
                 }
             }
         }
-        None => {
+        Outcome::Unsolved => {
             if is_intermediate_result {
-                print!("{part}: ✖");
+                print_progress(&format!("{part}: ✖"));
             } else {
-                print!("\r");
+                clear_progress();
                 println!("{part}: ✖             ");
             }
         }
+        Outcome::Failed(e) => {
+            if is_intermediate_result {
+                print_progress(&format!("{part}: ✖ failed"));
+            } else {
+                clear_progress();
+                println!("{part}: ✖ failed{duration_str}");
+                eprintln!("{part} error: {e}");
+                for cause in e.chain().skip(1) {
+                    eprintln!("  caused by: {cause}");
+                }
+            }
+        }
     }
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
-    day: Day,
-    part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
-    let args: Vec<String> = env::args().collect();
-
-    if !args.contains(&"--submit".into()) {
-        return None;
+/// Store the answer of one part in `data/answers.json`, so `cargo verify` can check it later.
+fn accept_answer(puzzle: Puzzle, part: u8, answer: &str) {
+    match answers::accept(puzzle, part, answer) {
+        Ok(()) => println!("Accepted answer `{answer}` for part {part}."),
+        Err(e) => eprintln!("Failed to store answer: {e}"),
     }
+}
 
-    if args.len() < 3 {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+/// Try to submit one part of the solution if:
+///  1. `--submit <part>` was passed for this part.
+///  2. a session cookie is configured.
+///  3. the submission log does not show that the answer is wrong, or that we have to wait.
+fn submit_result(result: String, puzzle: Puzzle, part: u8, options: &RunOptions) {
+    if options.submit != Some(part) {
+        return;
     }
 
-    let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
+    let client = AocClient::from_env();
 
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+    let mut log = match SubmissionLog::read_from_file(puzzle) {
+        Ok(log) => log,
+        Err(e) => {
+            eprintln!("Failed to read submission log: {e}");
+            process::exit(1);
+        }
     };
 
-    if part_submit != part {
-        return None;
+    if let Err(refusal) = log.check(part, &result, unix_now()) {
+        eprintln!("{ANSI_BOLD}Not submitting `{result}`:{ANSI_RESET} {refusal}");
+        return;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    println!("Submitting result...");
+    let response = client.submit(puzzle, part, &result);
+
+    if let Some(attempt) = Attempt::from_result(part, &result, &response, unix_now()) {
+        log.attempts.push(attempt);
+        if let Err(e) = log.store_file(puzzle) {
+            eprintln!("Failed to store submission log: {e}");
+        }
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    match response {
+        Ok(message) => {
+            println!("{message}");
+            accept_answer(puzzle, part, &result);
+        }
+        Err(e) => eprintln!("{ANSI_BOLD}Submission failed:{ANSI_RESET} {e}"),
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{BenchLimit, BenchOptions, InputSource};
+    use std::{ffi::OsString, time::Duration};
+
+    fn parse(args: Vec<String>) -> BenchOptions {
+        parse_with_defaults(args, BenchOptions::default())
+    }
+
+    fn parse_with_defaults(args: Vec<String>, defaults: BenchOptions) -> BenchOptions {
+        let args = args.into_iter().map(OsString::from).collect();
+        let mut args = pico_args::Arguments::from_vec(args);
+        BenchOptions::parse_with_defaults(&mut args, defaults).unwrap()
+    }
+
+    #[test]
+    fn bench_options_keep_defaults() {
+        let defaults = BenchOptions {
+            limit: BenchLimit::Samples(7),
+            warmup: 5,
+            reject_outliers: true,
+        };
+        assert_eq!(parse_with_defaults(vec![], defaults), defaults);
+
+        let overridden = parse_with_defaults(vec!["--warmup".into(), "0".into()], defaults);
+        assert_eq!(overridden.warmup, 0);
+        assert!(overridden.reject_outliers);
+    }
+
+    #[test]
+    fn bench_options_round_trip() {
+        let options = [
+            BenchOptions::default(),
+            BenchOptions {
+                limit: BenchLimit::Samples(42),
+                warmup: 0,
+                reject_outliers: true,
+            },
+            BenchOptions {
+                limit: BenchLimit::Budget(Duration::from_millis(250)),
+                warmup: 3,
+                reject_outliers: false,
+            },
+        ];
+
+        for option in options {
+            assert_eq!(parse(option.to_args()), option);
+        }
+    }
+
+    fn parse_input(args: &[&str]) -> Result<InputSource, pico_args::Error> {
+        let args = args.iter().map(OsString::from).collect();
+        InputSource::parse(&mut pico_args::Arguments::from_vec(args))
+    }
+
+    #[test]
+    fn parses_input_sources() {
+        assert_eq!(parse_input(&[]).unwrap(), InputSource::Puzzle);
+        assert_eq!(
+            parse_input(&["--example"]).unwrap(),
+            InputSource::Example(None)
+        );
+        assert_eq!(
+            parse_input(&["--example", "3"]).unwrap(),
+            InputSource::Example(Some(3))
+        );
+        assert_eq!(
+            parse_input(&["--example", "--time"]).unwrap(),
+            InputSource::Example(None)
+        );
+        assert_eq!(parse_input(&["--stdin"]).unwrap(), InputSource::Stdin);
+        assert!(parse_input(&["--stdin", "--example"]).is_err());
+    }
+
+    #[test]
+    fn input_source_round_trip() {
+        let sources = [
+            InputSource::Puzzle,
+            InputSource::Example(None),
+            InputSource::Example(Some(2)),
+            InputSource::File("data/custom.txt".into()),
+            InputSource::Stdin,
+        ];
+
+        for source in sources {
+            let args: Vec<String> = source.to_args();
+            let args: Vec<&str> = args.iter().map(String::as_str).collect();
+            assert_eq!(parse_input(&args).unwrap(), source);
+        }
+    }
 }
@@ -0,0 +1,213 @@
+/// Summary statistics for benchmark samples.
+use std::{collections::HashMap, time::Duration};
+use tinyjson::JsonValue;
+
+/// Tukey's fence factor: samples further than `1.5 * IQR` outside the quartiles are outliers.
+const TUKEY_FENCE: f64 = 1.5;
+
+/// Statistics over a set of benchmark samples. All durations are in nanoseconds.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct BenchStats {
+    /// Number of samples the statistics are computed from.
+    pub samples: u128,
+    /// Number of samples that were rejected as outliers.
+    pub outliers: u128,
+    pub mean: f64,
+    pub median: f64,
+    pub min: f64,
+    pub max: f64,
+    pub std_dev: f64,
+    pub p95: f64,
+}
+
+impl BenchStats {
+    /// Statistics for a single, unbenched run.
+    pub fn single(nanos: f64) -> Self {
+        Self {
+            samples: 1,
+            outliers: 0,
+            mean: nanos,
+            median: nanos,
+            min: nanos,
+            max: nanos,
+            std_dev: 0.0,
+            p95: nanos,
+        }
+    }
+
+    /// Compute statistics for `samples`, optionally discarding outliers outside Tukey's fences.
+    /// Returns `None` if there are no samples.
+    pub fn from_samples(samples: &[Duration], reject_outliers: bool) -> Option<Self> {
+        #[allow(clippy::cast_precision_loss)]
+        let mut sorted: Vec<f64> = samples.iter().map(|d| d.as_nanos() as f64).collect();
+        sorted.sort_unstable_by(f64::total_cmp);
+
+        let total = sorted.len();
+
+        if reject_outliers {
+            let q1 = percentile(&sorted, 25.0)?;
+            let q3 = percentile(&sorted, 75.0)?;
+            let iqr = q3 - q1;
+            let (low, high) = (q1 - TUKEY_FENCE * iqr, q3 + TUKEY_FENCE * iqr);
+            sorted.retain(|x| (low..=high).contains(x));
+        }
+
+        let n = sorted.len();
+        #[allow(clippy::cast_precision_loss)]
+        let mean = sorted.iter().sum::<f64>() / n as f64;
+        #[allow(clippy::cast_precision_loss)]
+        let variance = sorted.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / n as f64;
+
+        Some(Self {
+            samples: n as u128,
+            outliers: (total - n) as u128,
+            mean,
+            median: percentile(&sorted, 50.0)?,
+            min: *sorted.first()?,
+            max: *sorted.last()?,
+            std_dev: variance.sqrt(),
+            p95: percentile(&sorted, 95.0)?,
+        })
+    }
+}
+
+/// Linearly interpolated percentile of an ascending slice.
+fn percentile(sorted: &[f64], p: f64) -> Option<f64> {
+    let last = sorted.len().checked_sub(1)?;
+    #[allow(clippy::cast_precision_loss)]
+    let rank = p / 100.0 * last as f64;
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+    let weight = rank - rank.floor();
+    Some(sorted[lower] + (sorted[upper] - sorted[lower]) * weight)
+}
+
+/// Format a number of nanoseconds like a [`Duration`], e.g. `74.1µs`.
+pub fn format_nanos(nanos: f64) -> String {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let duration = Duration::from_nanos(nanos.round() as u64);
+    format!("{duration:.1?}")
+}
+
+/// Parse a duration formatted like a [`Duration`], e.g. `74.1µs`, to nanoseconds.
+pub fn parse_nanos(s: &str) -> Option<f64> {
+    // for possible formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    let s = s.trim();
+    let (value, factor) = if let Some(v) = s.strip_suffix("ns") {
+        (v, 1_f64)
+    } else if let Some(v) = s.strip_suffix("µs") {
+        (v, 1_000_f64)
+    } else if let Some(v) = s.strip_suffix("ms") {
+        (v, 1_000_000_f64)
+    } else {
+        (s.strip_suffix('s')?, 1_000_000_000_f64)
+    };
+    value.parse::<f64>().ok().map(|x| x * factor)
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&BenchStats> for JsonValue {
+    fn from(value: &BenchStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        {
+            map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+            map.insert("outliers".into(), JsonValue::Number(value.outliers as f64));
+        }
+        map.insert("mean".into(), JsonValue::Number(value.mean));
+        map.insert("median".into(), JsonValue::Number(value.median));
+        map.insert("min".into(), JsonValue::Number(value.min));
+        map.insert("max".into(), JsonValue::Number(value.max));
+        map.insert("std_dev".into(), JsonValue::Number(value.std_dev));
+        map.insert("p95".into(), JsonValue::Number(value.p95));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for BenchStats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected stats.{key} to be a number."))
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(BenchStats {
+            samples: number("samples")? as u128,
+            outliers: number("outliers")? as u128,
+            mean: number("mean")?,
+            median: number("median")?,
+            min: number("min")?,
+            max: number("max")?,
+            std_dev: number("std_dev")?,
+            p95: number("p95")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_nanos, parse_nanos, BenchStats};
+    use std::time::Duration;
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|x| Duration::from_nanos(*x)).collect()
+    }
+
+    #[test]
+    fn computes_summary_statistics() {
+        let stats = BenchStats::from_samples(&nanos(&[5, 1, 4, 2, 3]), false).unwrap();
+        assert_eq!(stats.samples, 5);
+        assert_eq!(stats.outliers, 0);
+        assert_eq!(stats.mean, 3.0);
+        assert_eq!(stats.median, 3.0);
+        assert_eq!(stats.min, 1.0);
+        assert_eq!(stats.max, 5.0);
+        assert_eq!(stats.std_dev, 2_f64.sqrt());
+        assert_eq!(stats.p95, 4.8);
+    }
+
+    #[test]
+    fn rejects_outliers() {
+        let samples = nanos(&[10, 11, 12, 10, 11, 12, 10, 11, 1000]);
+        let kept = BenchStats::from_samples(&samples, false).unwrap();
+        assert_eq!(kept.max, 1000.0);
+
+        let rejected = BenchStats::from_samples(&samples, true).unwrap();
+        assert_eq!(rejected.samples, 8);
+        assert_eq!(rejected.outliers, 1);
+        assert_eq!(rejected.max, 12.0);
+    }
+
+    #[test]
+    fn handles_empty_samples() {
+        assert_eq!(BenchStats::from_samples(&[], false), None);
+    }
+
+    #[test]
+    fn formats_nanos() {
+        assert_eq!(format_nanos(74.0), "74.0ns");
+        assert_eq!(format_nanos(74_130_000.0), "74.1ms");
+    }
+
+    #[test]
+    fn parses_nanos() {
+        assert_eq!(parse_nanos("74.13ns"), Some(74.13));
+        assert_eq!(parse_nanos("1.5µs"), Some(1_500.0));
+        assert_eq!(parse_nanos("100ms"), Some(100_000_000.0));
+        assert_eq!(parse_nanos("2s"), Some(2_000_000_000.0));
+        assert_eq!(parse_nanos("fast"), None);
+    }
+}
@@ -0,0 +1,452 @@
+/// Per-day log of submitted answers, stored in `data/submissions/<day>.json`.
+///
+/// The log is used to guard against submissions the website is known to reject: answers that
+/// were already rejected, answers outside of the bounds given by "too high" / "too low" hints,
+/// and submissions before the waiting time of a previous attempt has passed.
+use std::{
+    collections::HashMap, fmt::Display, fs, io, path::PathBuf, str::FromStr, time::Duration,
+};
+use tinyjson::JsonValue;
+
+use crate::template::{
+    aoc_client::{AocClientError, Hint},
+    Puzzle,
+};
+
+/// Name of the submissions directory in the data directory.
+static SUBMISSIONS_DIR_NAME: &str = "submissions";
+
+/// The website's response to a submitted answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Response {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint.
+    Wrong,
+    /// The answer was not checked because a previous attempt was too recent.
+    RateLimited,
+    /// The answer was not checked because the part is not available for solving.
+    WrongLevel,
+}
+
+impl Response {
+    fn is_rejection(self) -> bool {
+        matches!(self, Response::TooHigh | Response::TooLow | Response::Wrong)
+    }
+}
+
+impl Display for Response {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Response::Correct => "correct",
+            Response::TooHigh => "too_high",
+            Response::TooLow => "too_low",
+            Response::Wrong => "wrong",
+            Response::RateLimited => "rate_limited",
+            Response::WrongLevel => "wrong_level",
+        })
+    }
+}
+
+impl FromStr for Response {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Response::Correct),
+            "too_high" => Ok(Response::TooHigh),
+            "too_low" => Ok(Response::TooLow),
+            "wrong" => Ok(Response::Wrong),
+            "rate_limited" => Ok(Response::RateLimited),
+            "wrong_level" => Ok(Response::WrongLevel),
+            _ => Err(format!("`{s}` is not a valid submission response.")),
+        }
+    }
+}
+
+/// A single submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Attempt {
+    pub part: u8,
+    pub answer: String,
+    pub response: Response,
+    /// Time of the submission, in seconds since the unix epoch.
+    pub timestamp: u64,
+    /// Time until which the website does not accept further answers, in seconds since the
+    /// unix epoch.
+    pub wait_until: Option<u64>,
+}
+
+impl Attempt {
+    /// Build an attempt from the client's result of a submission.
+    /// Returns `None` for errors that happened before the answer reached the website.
+    pub fn from_result(
+        part: u8,
+        answer: &str,
+        result: &Result<String, AocClientError>,
+        timestamp: u64,
+    ) -> Option<Self> {
+        let (response, wait) = match result {
+            Ok(_) => (Response::Correct, None),
+            Err(AocClientError::WrongAnswer { hint, wait }) => match hint {
+                Some(Hint::TooHigh) => (Response::TooHigh, *wait),
+                Some(Hint::TooLow) => (Response::TooLow, *wait),
+                None => (Response::Wrong, *wait),
+            },
+            Err(AocClientError::RateLimited { wait }) => (Response::RateLimited, *wait),
+            Err(AocClientError::WrongLevel) => (Response::WrongLevel, None),
+            Err(_) => return None,
+        };
+
+        Some(Attempt {
+            part,
+            answer: answer.into(),
+            response,
+            timestamp,
+            wait_until: wait.map(|wait| timestamp + wait.as_secs()),
+        })
+    }
+}
+
+/// Reason for not submitting an answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Refusal {
+    /// The part was already solved.
+    AlreadySolved { answer: String },
+    /// The same answer was rejected before.
+    Duplicate { response: Response },
+    /// The answer is at or above an answer that was too high.
+    TooHigh { bound: String },
+    /// The answer is at or below an answer that was too low.
+    TooLow { bound: String },
+    /// The website asked to wait before the next submission.
+    Wait { remaining: Duration },
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::AlreadySolved { answer } => {
+                write!(f, "this part was already solved with `{answer}`.")
+            }
+            Refusal::Duplicate { response } => {
+                write!(
+                    f,
+                    "this answer was already submitted and rejected ({response})."
+                )
+            }
+            Refusal::TooHigh { bound } => {
+                write!(f, "`{bound}` was already too high, so this answer is too.")
+            }
+            Refusal::TooLow { bound } => {
+                write!(f, "`{bound}` was already too low, so this answer is too.")
+            }
+            Refusal::Wait { remaining } => write!(
+                f,
+                "please wait {}s before submitting again.",
+                remaining.as_secs()
+            ),
+        }
+    }
+}
+
+/// All submissions of a single day.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SubmissionLog {
+    pub attempts: Vec<Attempt>,
+}
+
+impl SubmissionLog {
+    /// Dehydrate the log of `puzzle` to a JSON file.
+    pub fn store_file(&self, puzzle: Puzzle) -> Result<(), io::Error> {
+        fs::create_dir_all(puzzle.paths().data_file(SUBMISSIONS_DIR_NAME))?;
+        let json = JsonValue::from(self);
+        let mut file = fs::File::create(get_path(puzzle))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate the log of `puzzle` from a JSON file. If not present, returns an empty log.
+    pub fn read_from_file(puzzle: Puzzle) -> Result<Self, String> {
+        match fs::read_to_string(get_path(puzzle)) {
+            Ok(s) => SubmissionLog::try_from(s),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(SubmissionLog::default()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    /// Check whether `answer` should be submitted for `part` at time `now`.
+    pub fn check(&self, part: u8, answer: &str, now: u64) -> Result<(), Refusal> {
+        let attempts = || self.attempts.iter().filter(|a| a.part == part);
+
+        if let Some(correct) = attempts().find(|a| a.response == Response::Correct) {
+            return Err(Refusal::AlreadySolved {
+                answer: correct.answer.clone(),
+            });
+        }
+
+        if let Some(duplicate) =
+            attempts().find(|a| a.answer == answer && a.response.is_rejection())
+        {
+            return Err(Refusal::Duplicate {
+                response: duplicate.response,
+            });
+        }
+
+        if let Ok(value) = answer.parse::<i128>() {
+            let bound = |response: Response| {
+                attempts()
+                    .filter(move |a| a.response == response)
+                    .filter_map(|a| Some((a.answer.parse::<i128>().ok()?, &a.answer)))
+            };
+
+            if let Some((_, bound)) = bound(Response::TooHigh)
+                .filter(|(high, _)| value >= *high)
+                .min_by_key(|(high, _)| *high)
+            {
+                return Err(Refusal::TooHigh {
+                    bound: bound.clone(),
+                });
+            }
+
+            if let Some((_, bound)) = bound(Response::TooLow)
+                .filter(|(low, _)| value <= *low)
+                .max_by_key(|(low, _)| *low)
+            {
+                return Err(Refusal::TooLow {
+                    bound: bound.clone(),
+                });
+            }
+        }
+
+        // the waiting time applies to all parts of all days, but is only tracked per day.
+        if let Some(wait_until) = self
+            .attempts
+            .iter()
+            .filter_map(|a| a.wait_until)
+            .max()
+            .filter(|wait_until| *wait_until > now)
+        {
+            return Err(Refusal::Wait {
+                remaining: Duration::from_secs(wait_until - now),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+fn get_path(puzzle: Puzzle) -> PathBuf {
+    puzzle
+        .paths()
+        .data_file(SUBMISSIONS_DIR_NAME)
+        .join(format!("{}.json", puzzle.day))
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&SubmissionLog> for JsonValue {
+    fn from(value: &SubmissionLog) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "attempts".into(),
+            JsonValue::Array(value.attempts.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for SubmissionLog {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_attempts = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("attempts")
+            .ok_or("expected JSON document to have key `attempts`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.attempts` to be an array.")?;
+
+        Ok(SubmissionLog {
+            attempts: json_attempts
+                .iter()
+                .map(Attempt::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Attempt> for JsonValue {
+    fn from(value: &Attempt) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("part".into(), JsonValue::Number(value.part.into()));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "response".into(),
+            JsonValue::String(value.response.to_string()),
+        );
+        #[allow(clippy::cast_precision_loss)]
+        {
+            map.insert(
+                "timestamp".into(),
+                JsonValue::Number(value.timestamp as f64),
+            );
+            map.insert(
+                "wait_until".into(),
+                match value.wait_until {
+                    Some(x) => JsonValue::Number(x as f64),
+                    None => JsonValue::Null,
+                },
+            );
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Attempt {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected attempt to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected attempt.{key} to be a number."))
+        };
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected attempt.answer to be a string.")?;
+
+        let response = json
+            .get("response")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected attempt.response to be a string.")?
+            .parse()?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let wait_until = json
+            .get("wait_until")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u64);
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(Attempt {
+            part: number("part")? as u8,
+            answer: answer.clone(),
+            response,
+            timestamp: number("timestamp")? as u64,
+            wait_until,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Attempt, Refusal, Response, SubmissionLog};
+    use crate::template::aoc_client::{AocClientError, Hint};
+    use std::time::Duration;
+
+    fn attempt(part: u8, answer: &str, response: Response, wait_until: Option<u64>) -> Attempt {
+        Attempt {
+            part,
+            answer: answer.into(),
+            response,
+            timestamp: 100,
+            wait_until,
+        }
+    }
+
+    fn get_mock_log() -> SubmissionLog {
+        SubmissionLog {
+            attempts: vec![
+                attempt(1, "500", Response::TooHigh, Some(160)),
+                attempt(1, "100", Response::TooLow, None),
+                attempt(1, "abc", Response::Wrong, None),
+                attempt(2, "7", Response::Correct, None),
+            ],
+        }
+    }
+
+    #[test]
+    fn refuses_known_answers() {
+        let log = get_mock_log();
+        assert_eq!(
+            log.check(2, "8", 200),
+            Err(Refusal::AlreadySolved { answer: "7".into() })
+        );
+        assert_eq!(
+            log.check(1, "abc", 200),
+            Err(Refusal::Duplicate {
+                response: Response::Wrong
+            })
+        );
+    }
+
+    #[test]
+    fn refuses_answers_outside_bounds() {
+        let log = get_mock_log();
+        assert_eq!(
+            log.check(1, "600", 200),
+            Err(Refusal::TooHigh {
+                bound: "500".into()
+            })
+        );
+        assert_eq!(
+            log.check(1, "99", 200),
+            Err(Refusal::TooLow {
+                bound: "100".into()
+            })
+        );
+        assert_eq!(log.check(1, "250", 200), Ok(()));
+    }
+
+    #[test]
+    fn refuses_while_waiting() {
+        let log = get_mock_log();
+        assert_eq!(
+            log.check(1, "250", 130),
+            Err(Refusal::Wait {
+                remaining: Duration::from_secs(30)
+            })
+        );
+    }
+
+    #[test]
+    fn builds_attempts_from_results() {
+        let result = Err(AocClientError::WrongAnswer {
+            hint: Some(Hint::TooLow),
+            wait: Some(Duration::from_secs(60)),
+        });
+        assert_eq!(
+            Attempt::from_result(1, "5", &result, 100),
+            Some(attempt(1, "5", Response::TooLow, Some(160)))
+        );
+        assert_eq!(
+            Attempt::from_result(1, "5", &Err(AocClientError::Unauthorized), 100),
+            None
+        );
+    }
+
+    #[test]
+    fn round_trips_json() {
+        let log = get_mock_log();
+        let json = tinyjson::JsonValue::from(&log).stringify().unwrap();
+        assert_eq!(SubmissionLog::try_from(json).unwrap(), log);
+    }
+}
@@ -0,0 +1,120 @@
+/// Templates for new solutions, used by `cargo scaffold`.
+///
+/// The built-in `default` template is `src/template.txt`. Named templates are read from
+/// `.aoc/templates/<name>.txt` and take precedence over the built-in one.
+use std::{fs, io, path::Path};
+
+use crate::template::Puzzle;
+
+pub const TEMPLATES_DIR: &str = ".aoc/templates";
+pub const DEFAULT_TEMPLATE: &str = "default";
+
+const BUILTIN_TEMPLATE: &str =
+    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+
+/// Values that are substituted into a template.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Placeholders {
+    pub puzzle: Puzzle,
+    /// The puzzle title, if the description has been downloaded.
+    pub title: Option<String>,
+    pub answer_type: String,
+}
+
+impl Placeholders {
+    fn replacements(&self) -> [(&'static str, String); 5] {
+        let day = self.puzzle.day.into_inner();
+
+        [
+            ("%YEAR%", self.puzzle.year.to_string()),
+            ("%DAY%", self.puzzle.day.to_string()),
+            ("%DAY_NUMBER%", day.to_string()),
+            (
+                "%TITLE%",
+                self.title.clone().unwrap_or_else(|| format!("Day {day}")),
+            ),
+            ("%ANSWER_TYPE%", self.answer_type.clone()),
+        ]
+    }
+}
+
+/// Read the template `name`.
+pub fn load(name: &str) -> Result<String, String> {
+    let path = Path::new(TEMPLATES_DIR).join(format!("{name}.txt"));
+
+    match fs::read_to_string(&path) {
+        Ok(template) => Ok(template),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            if name == DEFAULT_TEMPLATE {
+                Ok(BUILTIN_TEMPLATE.into())
+            } else {
+                Err(format!(
+                    "unknown template `{name}`, available templates: {}.",
+                    available().join(", ")
+                ))
+            }
+        }
+        Err(e) => Err(format!("failed to read \"{}\": {e}", path.display())),
+    }
+}
+
+/// The names of all templates, sorted.
+pub fn available() -> Vec<String> {
+    let mut names = vec![DEFAULT_TEMPLATE.to_string()];
+
+    if let Ok(entries) = fs::read_dir(TEMPLATES_DIR) {
+        names.extend(entries.filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let name = path.file_stem()?.to_str()?;
+            (path.extension()? == "txt").then(|| name.to_string())
+        }));
+    }
+
+    names.sort_unstable();
+    names.dedup();
+    names
+}
+
+/// Substitute `%YEAR%`, `%DAY%` (zero-padded), `%DAY_NUMBER%`, `%TITLE%` and `%ANSWER_TYPE%`.
+/// A missing title is replaced with `Day N`.
+pub fn render(template: &str, placeholders: &Placeholders) -> String {
+    placeholders
+        .replacements()
+        .iter()
+        .fold(template.to_string(), |s, (key, value)| {
+            s.replace(key, value)
+        })
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{load, render, Placeholders, DEFAULT_TEMPLATE};
+    use crate::{day, template::Puzzle};
+
+    #[test]
+    fn renders_placeholders() {
+        let mut placeholders = Placeholders {
+            puzzle: Puzzle::new(2017, day!(4)),
+            title: Some("High-Entropy Passphrases".into()),
+            answer_type: "usize".into(),
+        };
+
+        let template =
+            "// %YEAR% day %DAY%: %TITLE%\nsolution!(%DAY_NUMBER%);\nfn f() -> %ANSWER_TYPE% {}";
+        assert_eq!(
+            render(template, &placeholders),
+            "// 2017 day 04: High-Entropy Passphrases\nsolution!(4);\nfn f() -> usize {}"
+        );
+
+        placeholders.title = None;
+        assert_eq!(render("%TITLE%", &placeholders), "Day 4");
+    }
+
+    #[test]
+    fn loads_templates() {
+        assert!(load(DEFAULT_TEMPLATE).unwrap().contains("%DAY_NUMBER%"));
+        assert!(load("does-not-exist").is_err());
+    }
+}
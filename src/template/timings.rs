@@ -1,19 +1,103 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    io::{self, Error},
+    str::FromStr,
+};
 use tinyjson::JsonValue;
 
+use crate::template::alloc_stats::AllocStats;
+use crate::template::config;
+use crate::template::report::{PartReport, Status, PARSE_PART};
+use crate::template::run_multi::LimitExceeded;
+use crate::template::stats::{parse_nanos, BenchStats};
 use crate::template::Day;
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+/// Name of the timings file in the data directory.
+static TIMINGS_FILE_NAME: &str = "timings.json";
+
+/// Version of the `timings.json` schema written by this template.
+///
+///  1. `part_1` and `part_2` are formatted durations, e.g. `"74.1ms"`. Files don't have a version.
+///  2. parts are objects with the statistics of all samples, in nanoseconds.
+pub const TIMINGS_VERSION: u32 = 2;
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
+    /// Timing of the shared parse step, for solutions that declare one.
+    pub parse: Option<BenchStats>,
+    pub part_1: Option<BenchStats>,
+    pub part_2: Option<BenchStats>,
+    /// Parts that returned an error, [`PARSE_PART`] for the parse step.
+    pub failed_parts: Vec<u8>,
+    /// The limit the day was stopped at, only the parts that finished before have timings.
+    pub exceeded: Option<LimitExceeded>,
+    /// Allocations of each part, [`PARSE_PART`] for the parse step. Only recorded with the
+    /// `alloc-stats` feature.
+    pub alloc: BTreeMap<u8, AllocStats>,
+    /// Sum of the median run time of all parts.
     pub total_nanos: f64,
 }
 
+impl Timing {
+    /// Build the timing for a day from the reports emitted by its solution parts.
+    /// Parts without an answer are left empty and do not count towards the total.
+    pub fn from_reports(day: Day, reports: &[PartReport]) -> Self {
+        let mut timing = Timing {
+            day,
+            parse: None,
+            part_1: None,
+            part_2: None,
+            failed_parts: vec![],
+            exceeded: None,
+            alloc: BTreeMap::new(),
+            total_nanos: 0_f64,
+        };
+
+        timing.failed_parts = reports
+            .iter()
+            .filter(|r| r.day == day && r.status == Status::Failed)
+            .map(|r| r.part)
+            .collect();
+
+        for report in reports
+            .iter()
+            .filter(|r| r.day == day && r.status == Status::Solved)
+        {
+            let stats = report
+                .stats
+                .unwrap_or_else(|| BenchStats::single(report.duration_nanos));
+
+            match report.part {
+                PARSE_PART => timing.parse = Some(stats),
+                1 => timing.part_1 = Some(stats),
+                2 => timing.part_2 = Some(stats),
+                _ => continue,
+            }
+
+            if let Some(alloc) = report.alloc {
+                timing.alloc.insert(report.part, alloc);
+            }
+
+            timing.total_nanos += stats.median;
+        }
+
+        timing
+    }
+
+    /// Statistics of `part`, or of the parse step for [`PARSE_PART`].
+    pub fn part(&self, part: u8) -> Option<&BenchStats> {
+        match part {
+            PARSE_PART => self.parse.as_ref(),
+            1 => self.part_1.as_ref(),
+            2 => self.part_2.as_ref(),
+            _ => None,
+        }
+    }
+}
+
 /// Represents benchmark times for a set of days.
 /// Can be serialized from / to JSON.
 #[derive(Clone, Debug, Default)]
@@ -22,25 +106,21 @@ pub struct Timings {
 }
 
 impl Timings {
-    /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
+    /// Dehydrate the timings of `year` to a JSON file.
+    pub fn store_file(&self, year: u16) -> Result<(), Error> {
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let mut file = config::get()
+            .paths_for(year)
+            .create_data_file(TIMINGS_FILE_NAME)?;
         json.format_to(&mut file)
     }
 
-    /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        let s = fs::read_to_string(TIMINGS_FILE_PATH)
-            .map_err(|x| x.to_string())
-            .and_then(Timings::try_from);
-
-        match s {
-            Ok(timings) => timings,
-            Err(e) => {
-                eprintln!("{e}");
-                Timings::default()
-            }
+    /// Rehydrate the timings of `year` from a JSON file. If not present, returns empty timings.
+    pub fn read_from_file(year: u16) -> Result<Self, String> {
+        match fs::read_to_string(config::get().paths_for(year).data_file(TIMINGS_FILE_NAME)) {
+            Ok(s) => Timings::try_from(s),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Timings::default()),
+            Err(e) => Err(e.to_string()),
         }
     }
 
@@ -58,7 +138,7 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
@@ -67,10 +147,11 @@ impl Timings {
         self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
     }
 
-    pub fn is_day_complete(&self, day: Day) -> bool {
+    /// Whether all of the given `parts` of `day` have been benched.
+    pub fn is_day_complete(&self, day: Day, parts: &[u8]) -> bool {
         self.data
             .iter()
-            .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
+            .any(|t| t.day == day && parts.iter().all(|p| t.part(*p).is_some()))
     }
 }
 
@@ -80,6 +161,7 @@ impl From<Timings> for JsonValue {
     fn from(value: Timings) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
+        map.insert("version".into(), JsonValue::Number(TIMINGS_VERSION.into()));
         map.insert(
             "data".into(),
             JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
@@ -95,13 +177,35 @@ impl TryFrom<String> for Timings {
     fn try_from(value: String) -> Result<Self, Self::Error> {
         let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
 
-        let json_data = json
+        let json = json
             .get::<HashMap<String, JsonValue>>()
-            .ok_or("expected JSON document to be an object.")?
+            .ok_or("expected JSON document to be an object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let version = match json.get("version") {
+            Some(v) => v
+                .get::<f64>()
+                .map(|v| *v as u32)
+                .ok_or("expected `json.version` to be a number.")?,
+            None => 1,
+        };
+
+        if version > TIMINGS_VERSION {
+            return Err(format!(
+                "timings were stored by a newer version of the template (v{version}, supported: v{TIMINGS_VERSION})."
+            ));
+        }
+
+        let mut json_data = json
             .get("data")
             .ok_or("expected JSON document to have key `data`.")?
             .get::<Vec<JsonValue>>()
-            .ok_or("expected `json.data` to be an array.")?;
+            .ok_or("expected `json.data` to be an array.")?
+            .clone();
+
+        if version < 2 {
+            json_data.iter_mut().try_for_each(migrate_v1)?;
+        }
 
         Ok(Timings {
             data: json_data
@@ -112,6 +216,22 @@ impl TryFrom<String> for Timings {
     }
 }
 
+/// Replace the formatted durations of a v1 timing with statistics of a single sample.
+fn migrate_v1(timing: &mut JsonValue) -> Result<(), String> {
+    let Some(json) = timing.get_mut::<HashMap<String, JsonValue>>() else {
+        return Ok(());
+    };
+
+    for key in ["part_1", "part_2"] {
+        if let Some(JsonValue::String(s)) = json.get(key) {
+            let nanos = parse_nanos(s).ok_or(format!("Expected timing.{key} to be a duration."))?;
+            json.insert(key.into(), JsonValue::from(&BenchStats::single(nanos)));
+        }
+    }
+
+    Ok(())
+}
+
 /* -------------------------------------------------------------------------- */
 
 impl From<&Timing> for JsonValue {
@@ -121,8 +241,17 @@ impl From<&Timing> for JsonValue {
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
 
-        let part_1 = value.part_1.clone().map(JsonValue::String);
-        let part_2 = value.part_2.clone().map(JsonValue::String);
+        let parse = value.parse.as_ref().map(JsonValue::from);
+        let part_1 = value.part_1.as_ref().map(JsonValue::from);
+        let part_2 = value.part_2.as_ref().map(JsonValue::from);
+
+        map.insert(
+            "parse".into(),
+            match parse {
+                Some(x) => x,
+                None => JsonValue::Null,
+            },
+        );
 
         map.insert(
             "part_1".into(),
@@ -140,6 +269,36 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        map.insert(
+            "failed_parts".into(),
+            JsonValue::Array(
+                value
+                    .failed_parts
+                    .iter()
+                    .map(|p| JsonValue::Number((*p).into()))
+                    .collect(),
+            ),
+        );
+
+        map.insert(
+            "exceeded".into(),
+            match value.exceeded {
+                Some(limit) => JsonValue::String(limit.key().into()),
+                None => JsonValue::Null,
+            },
+        );
+
+        map.insert(
+            "alloc".into(),
+            JsonValue::Object(
+                value
+                    .alloc
+                    .iter()
+                    .filter_map(|(part, alloc)| Some((part_key(*part)?.into(), alloc.into())))
+                    .collect(),
+            ),
+        );
+
         JsonValue::Object(map)
     }
 }
@@ -158,15 +317,47 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|day| Day::from_str(day).ok())
             .ok_or("Expected timing.day to be a Day struct.")?;
 
-        let part_1 = json
-            .get("part_1")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_1 to be null or string.")?;
+        // `parse` was added later, older files do not contain it.
+        let parse = match json.get("parse") {
+            Some(_) => parse_part(json, "parse")?,
+            None => None,
+        };
+        let part_1 = parse_part(json, "part_1")?;
+        let part_2 = parse_part(json, "part_2")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let failed_parts = json
+            .get("failed_parts")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .map(|parts| {
+                parts
+                    .iter()
+                    .filter_map(|p| p.get::<f64>().map(|p| *p as u8))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        // `exceeded` was added later, older files do not contain it.
+        let exceeded = match json.get("exceeded") {
+            Some(JsonValue::String(limit)) => Some(limit.parse()?),
+            Some(v) if !v.is_null() => {
+                return Err("Expected timing.exceeded to be null or a string.".into())
+            }
+            _ => None,
+        };
 
-        let part_2 = json
-            .get("part_2")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_2 to be null or string.")?;
+        // `alloc` was added later, older files do not contain it.
+        let mut alloc = BTreeMap::new();
+        if let Some(v) = json.get("alloc") {
+            let parts = v
+                .get::<HashMap<String, JsonValue>>()
+                .ok_or("Expected timing.alloc to be an object.")?;
+            for part in [PARSE_PART, 1, 2] {
+                if let Some(stats) = part_key(part).and_then(|key| parts.get(key)) {
+                    alloc.insert(part, AllocStats::try_from(stats)?);
+                }
+            }
+        }
 
         let total_nanos = json
             .get("total_nanos")
@@ -175,18 +366,42 @@ impl TryFrom<&JsonValue> for Timing {
 
         Ok(Timing {
             day,
-            part_1: part_1.cloned(),
-            part_2: part_2.cloned(),
+            parse,
+            part_1,
+            part_2,
+            failed_parts,
+            exceeded,
+            alloc,
             total_nanos,
         })
     }
 }
 
+/// The key of `part` in a timing, e.g. `part_1`.
+fn part_key(part: u8) -> Option<&'static str> {
+    match part {
+        PARSE_PART => Some("parse"),
+        1 => Some("part_1"),
+        2 => Some("part_2"),
+        _ => None,
+    }
+}
+
+fn parse_part(json: &HashMap<String, JsonValue>, key: &str) -> Result<Option<BenchStats>, String> {
+    match json.get(key) {
+        Some(v) if v.is_null() => Ok(None),
+        Some(v) => BenchStats::try_from(v).map(Some),
+        None => Err(format!("Expected timing.{key} to be null or an object.")),
+    }
+}
+
 /* -------------------------------------------------------------------------- */
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use crate::day;
+    use std::collections::BTreeMap;
+
+    use crate::{day, template::stats::BenchStats};
 
     use super::{Timing, Timings};
 
@@ -195,20 +410,32 @@ mod tests {
             data: vec![
                 Timing {
                     day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
+                    parse: None,
+                    part_1: Some(BenchStats::single(10e6)),
+                    part_2: Some(BenchStats::single(20e6)),
+                    failed_parts: vec![],
+                    exceeded: None,
+                    alloc: BTreeMap::new(),
                     total_nanos: 3e+10,
                 },
                 Timing {
                     day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
+                    parse: None,
+                    part_1: Some(BenchStats::single(30e6)),
+                    part_2: Some(BenchStats::single(40e6)),
+                    failed_parts: vec![],
+                    exceeded: None,
+                    alloc: BTreeMap::new(),
                     total_nanos: 7e+10,
                 },
                 Timing {
                     day: day!(4),
-                    part_1: Some("40ms".into()),
+                    parse: None,
+                    part_1: Some(BenchStats::single(40e6)),
                     part_2: None,
+                    failed_parts: vec![],
+                    exceeded: None,
+                    alloc: BTreeMap::new(),
                     total_nanos: 4e+10,
                 },
             ],
@@ -216,20 +443,72 @@ mod tests {
     }
 
     mod deserialization {
-        use crate::{day, template::timings::Timings};
+        use crate::{
+            day,
+            template::{run_multi::LimitExceeded, stats::BenchStats, timings::Timings},
+        };
 
         #[test]
         fn handles_json_timings() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": { "samples": 1, "outliers": 0, "mean": 1e6, "median": 1e6, "min": 1e6, "max": 1e6, "std_dev": 0, "p95": 1e6 }, "part_2": null, "total_nanos": 1000000000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data.len(), 1);
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.day, day!(1));
+            assert_eq!(timing.part_1, Some(BenchStats::single(1e6)));
+            assert_eq!(timing.part_2, None);
+            assert_eq!(timing.total_nanos, 1_000_000_000_f64);
+        }
+
+        #[test]
+        fn handles_unversioned_json_timings() {
             let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000 }] }"#.to_string();
             let timings = Timings::try_from(json).unwrap();
             assert_eq!(timings.data.len(), 1);
             let timing = timings.data.first().unwrap();
             assert_eq!(timing.day, day!(1));
-            assert_eq!(timing.part_1, Some("1ms".to_string()));
+            assert_eq!(timing.part_1, Some(BenchStats::single(1e6)));
             assert_eq!(timing.part_2, None);
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn migrates_v1_timings() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "74.13ms", "part_2": "2s", "total_nanos": 2074130000 }, { "day": "02", "part_1": "1.5µs", "part_2": null, "total_nanos": 1500 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data.len(), 2);
+            assert_eq!(timings.data[0].part_1.unwrap().median, 74_130_000.0);
+            assert_eq!(timings.data[0].part_1.unwrap().samples, 1);
+            assert_eq!(timings.data[0].part_2, Some(BenchStats::single(2e9)));
+            assert_eq!(timings.data[1].part_1, Some(BenchStats::single(1_500.0)));
+            assert_eq!(timings.data[1].part_2, None);
+            assert_eq!(timings.data[1].parse, None);
+        }
+
+        #[test]
+        fn handles_exceeded_limits() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": null, "part_2": null, "exceeded": "timeout", "total_nanos": 0 }, { "day": "02", "part_1": null, "part_2": null, "exceeded": null, "total_nanos": 0 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data[0].exceeded, Some(LimitExceeded::Timeout));
+            assert_eq!(timings.data[1].exceeded, None);
+
+            let json = r#"{ "data": [{ "day": "01", "part_1": null, "part_2": null, "exceeded": "forever", "total_nanos": 0 }] }"#.to_string();
+            assert!(Timings::try_from(json).is_err());
+        }
+
+        #[test]
+        fn rejects_newer_versions() {
+            let json = r#"{ "version": 3, "data": [] }"#.to_string();
+            assert!(Timings::try_from(json).is_err());
+        }
+
+        #[test]
+        #[should_panic]
+        fn panics_for_malformed_v1_durations() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "fast", "part_2": null, "total_nanos": 0 }] }"#.to_string();
+            Timings::try_from(json).unwrap();
+        }
+
         #[test]
         fn handles_empty_timings() {
             let json = r#"{ "data": [] }"#.to_string();
@@ -254,9 +533,24 @@ mod tests {
 
     mod serialization {
         use super::get_mock_timings;
+        use crate::template::timings::{Timings, TIMINGS_VERSION};
         use std::collections::HashMap;
         use tinyjson::JsonValue;
 
+        #[test]
+        fn stores_version() {
+            let value = JsonValue::from(get_mock_timings());
+            let json = value.get::<HashMap<String, JsonValue>>().unwrap();
+            assert_eq!(
+                json.get("version").unwrap().get::<f64>(),
+                Some(&f64::from(TIMINGS_VERSION))
+            );
+
+            let parsed = Timings::try_from(value.stringify().unwrap()).unwrap();
+            assert_eq!(parsed.data.len(), 3);
+            assert_eq!(parsed.data[0].part_1, get_mock_timings().data[0].part_1);
+        }
+
         #[test]
         fn serializes_timings() {
             let timings = get_mock_timings();
@@ -276,9 +570,15 @@ mod tests {
     }
 
     mod is_day_complete {
+        use std::collections::BTreeMap;
+
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::{
+                registry::ALL_PARTS,
+                stats::BenchStats,
+                timings::{Timing, Timings},
+            },
         };
 
         #[test]
@@ -286,13 +586,17 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: Some("2ms".into()),
+                    parse: None,
+                    part_1: Some(BenchStats::single(1e6)),
+                    part_2: Some(BenchStats::single(2e6)),
+                    failed_parts: vec![],
+                    exceeded: None,
+                    alloc: BTreeMap::new(),
                     total_nanos: 3_000_000_000_f64,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1), ALL_PARTS), true);
         }
 
         #[test]
@@ -300,13 +604,18 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: Some("1ms".into()),
+                    parse: None,
+                    part_1: Some(BenchStats::single(1e6)),
                     part_2: None,
+                    failed_parts: vec![],
+                    exceeded: None,
+                    alloc: BTreeMap::new(),
                     total_nanos: 1_000_000_000_f64,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1), ALL_PARTS), false);
+            assert_eq!(timings.is_day_complete(day!(1), &[1]), true);
         }
 
         #[test]
@@ -314,17 +623,142 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: None,
                     part_2: None,
+                    failed_parts: vec![],
+                    exceeded: None,
+                    alloc: BTreeMap::new(),
                     total_nanos: 0.0,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1), ALL_PARTS), false);
+            assert_eq!(timings.is_day_complete(day!(1), &[1]), false);
+        }
+    }
+
+    mod from_reports {
+        use std::str::FromStr;
+        use tinyjson::JsonValue;
+
+        use crate::{
+            day,
+            template::{
+                alloc_stats::AllocStats,
+                report::{PartReport, Status, PARSE_PART},
+                stats::BenchStats,
+                timings::Timing,
+            },
+        };
+
+        fn report(part: u8, duration_nanos: f64, status: Status) -> PartReport {
+            let answer = "i=1;\tnum bridges: 2 (1.0ms @ 10 samples)";
+            PartReport {
+                duration_nanos,
+                ..PartReport::test(day!(1), part, Some(answer), status)
+            }
+        }
+
+        #[test]
+        fn collects_solved_parts() {
+            let stats = BenchStats {
+                samples: 10,
+                median: 2e9,
+                ..BenchStats::default()
+            };
+            let timing = Timing::from_reports(
+                day!(1),
+                &[
+                    report(1, 74_130_000.0, Status::Solved),
+                    PartReport {
+                        stats: Some(stats),
+                        ..report(2, 2e9, Status::Solved)
+                    },
+                ],
+            );
+            assert_eq!(timing.total_nanos, 2_074_130_000_f64);
+            assert_eq!(timing.part_1, Some(BenchStats::single(74_130_000.0)));
+            assert_eq!(timing.part_2, Some(stats));
+        }
+
+        #[test]
+        fn marks_failed_parts() {
+            let timing = Timing::from_reports(
+                day!(1),
+                &[
+                    report(1, 100.0, Status::Solved),
+                    PartReport {
+                        answer: None,
+                        error: Some("invalid digit found in string".into()),
+                        ..report(2, 100.0, Status::Failed)
+                    },
+                ],
+            );
+            assert_eq!(timing.failed_parts, vec![2]);
+            assert_eq!(timing.part_2, None);
+            assert_eq!(timing.total_nanos, 100_f64);
+        }
+
+        #[test]
+        fn collects_allocations() {
+            let alloc = AllocStats {
+                peak_bytes: 1024,
+                total_bytes: 2048,
+                count: 2,
+            };
+            let timing = Timing::from_reports(
+                day!(1),
+                &[
+                    PartReport {
+                        alloc: Some(alloc),
+                        ..report(1, 100.0, Status::Solved)
+                    },
+                    report(2, 100.0, Status::Solved),
+                ],
+            );
+            assert_eq!(timing.alloc.get(&1), Some(&alloc));
+            assert_eq!(timing.alloc.get(&2), None);
+
+            let json = JsonValue::from(&timing).stringify().unwrap();
+            let parsed = Timing::try_from(&JsonValue::from_str(&json).unwrap()).unwrap();
+            assert_eq!(parsed.alloc, timing.alloc);
+        }
+
+        #[test]
+        fn collects_parse_step() {
+            let timing = Timing::from_reports(
+                day!(1),
+                &[
+                    PartReport {
+                        answer: None,
+                        ..report(PARSE_PART, 1_000.0, Status::Solved)
+                    },
+                    report(1, 100.0, Status::Solved),
+                ],
+            );
+            assert_eq!(timing.total_nanos, 1_100_f64);
+            assert_eq!(timing.parse, Some(BenchStats::single(1_000.0)));
+        }
+
+        #[test]
+        fn skips_unsolved_parts() {
+            let timing = Timing::from_reports(
+                day!(1),
+                &[
+                    report(1, 100.0, Status::Solved),
+                    report(2, 100.0, Status::Unsolved),
+                ],
+            );
+            assert_eq!(timing.total_nanos, 100_f64);
+            assert_eq!(timing.part_1.unwrap().median, 100_f64);
+            assert_eq!(timing.part_2.is_none(), true);
         }
     }
 
     mod merge {
+        use std::collections::BTreeMap;
+
         use crate::{
             day,
             template::timings::{Timing, Timings},
@@ -338,8 +772,12 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(3),
+                    parse: None,
                     part_1: None,
                     part_2: None,
+                    failed_parts: vec![],
+                    exceeded: None,
+                    alloc: BTreeMap::new(),
                     total_nanos: 0_f64,
                 }],
             };
@@ -358,8 +796,12 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(2),
+                    parse: None,
                     part_1: None,
                     part_2: None,
+                    failed_parts: vec![],
+                    exceeded: None,
+                    alloc: BTreeMap::new(),
                     total_nanos: 0_f64,
                 }],
             };
@@ -0,0 +1,296 @@
+/// Watch mode for `solve --watch` and `test --watch`.
+///
+/// The files of a puzzle are polled for changes of their modification time, which works the
+/// same on every OS and needs no dependencies. Rapid saves are debounced: a run starts once the
+/// files have been quiet for [`DEBOUNCE`].
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::template::{
+    report::{PartReport, Status, PARSE_PART},
+    runner::InputSource,
+    Puzzle, ANSI_BOLD, ANSI_RESET,
+};
+
+/// How long the files have to be unchanged before a run starts.
+pub const DEBOUNCE: Duration = Duration::from_millis(300);
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+const ANSI_CLEAR: &str = "\x1b[2J\x1b[H";
+
+/// Modification times of the watched files. Missing files are left out, so that creating or
+/// deleting a file counts as a change.
+pub type Snapshot = BTreeMap<PathBuf, SystemTime>;
+
+/// The files that affect a run of `puzzle`: its solution, the helpers of the library, its input
+/// and its examples, including the manifest.
+pub fn snapshot(puzzle: Puzzle, input: &InputSource) -> Snapshot {
+    let paths = puzzle.paths();
+    let mut files = vec![PathBuf::from(format!("src/bin/{}.rs", puzzle.bin_name()))];
+
+    // `src/lib.rs` and its modules, but not the other solutions or the template.
+    collect_files(Path::new("src"), &mut files, &|path| {
+        path != Path::new("src/bin") && path != Path::new("src/template")
+    });
+
+    match input {
+        InputSource::File(path) => files.push(path.clone()),
+        _ => files.push(paths.input(puzzle.day)),
+    }
+
+    // examples are `NN.txt`, `NN-<n>.txt` or files named in the manifest `NN.toml`.
+    let day = puzzle.day.to_string();
+    collect_files(&paths.examples, &mut files, &|path| {
+        path.file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| {
+                name.starts_with(&format!("{day}.")) || name.starts_with(&format!("{day}-"))
+            })
+    });
+
+    files
+        .into_iter()
+        .filter_map(|path| {
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+            Some((path, modified))
+        })
+        .collect()
+}
+
+/// Push all files below `dir` for which `filter` holds, skipping directories it rejects.
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>, filter: &dyn Fn(&Path) -> bool) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for path in entries.filter_map(|entry| Some(entry.ok()?.path())) {
+        if !filter(&path) {
+            continue;
+        }
+        if path.is_dir() {
+            collect_files(&path, files, filter);
+        } else {
+            files.push(path);
+        }
+    }
+}
+
+/// The files that were added, modified or removed between two snapshots.
+pub fn changed_files(previous: &Snapshot, current: &Snapshot) -> Vec<PathBuf> {
+    let mut changed: Vec<PathBuf> = current
+        .iter()
+        .filter(|(path, modified)| previous.get(*path) != Some(modified))
+        .map(|(path, _)| path.clone())
+        .collect();
+    changed.extend(
+        previous
+            .keys()
+            .filter(|path| !current.contains_key(*path))
+            .cloned(),
+    );
+    changed.sort();
+    changed
+}
+
+/// Call `run` once, and again after every change to the files of `puzzle`. `run` receives the
+/// changed files. Never returns, watching ends with Ctrl-C.
+pub fn watch(puzzle: Puzzle, input: &InputSource, mut run: impl FnMut(&[PathBuf])) -> ! {
+    let mut previous = snapshot(puzzle, input);
+    run(&[]);
+
+    loop {
+        thread::sleep(POLL_INTERVAL);
+        let mut current = snapshot(puzzle, input);
+        if current == previous {
+            continue;
+        }
+
+        // wait for the editor, formatters and the like to finish writing.
+        loop {
+            thread::sleep(DEBOUNCE);
+            let next = snapshot(puzzle, input);
+            if next == current {
+                break;
+            }
+            current = next;
+        }
+
+        let changed = changed_files(&previous, &current);
+        previous = current;
+        run(&changed);
+    }
+}
+
+/// Clear the terminal and print what a run is about.
+pub fn print_header(puzzle: Puzzle, changed: &[PathBuf]) {
+    print!("{ANSI_CLEAR}");
+    let label = puzzle.label(!puzzle.is_primary());
+    println!("{ANSI_BOLD}Watching {label}{ANSI_RESET}, press Ctrl-C to stop.");
+
+    if !changed.is_empty() {
+        let changed: Vec<String> = changed.iter().map(|p| p.display().to_string()).collect();
+        println!("Changed: {}", changed.join(", "));
+    }
+    println!();
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// How the result of a part compares to the previous run.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Change {
+    /// There is no previous run to compare with.
+    First,
+    Unchanged,
+    /// The answer or status differs, `previous` describes the result of the previous run.
+    Changed {
+        previous: String,
+    },
+}
+
+/// Compare the answers of the parts of two runs.
+pub fn diff_answers(
+    previous: Option<&[PartReport]>,
+    current: &[PartReport],
+) -> Vec<(u8, String, Change)> {
+    current
+        .iter()
+        .filter(|report| report.part != PARSE_PART || report.status == Status::Failed)
+        .map(|report| {
+            let result = describe(Some(report));
+            let change = match previous {
+                None => Change::First,
+                Some(previous) => {
+                    let before = previous.iter().find(|p| p.part == report.part);
+                    let before = describe(before);
+                    if before == result {
+                        Change::Unchanged
+                    } else {
+                        Change::Changed { previous: before }
+                    }
+                }
+            };
+            (report.part, result, change)
+        })
+        .collect()
+}
+
+fn describe(report: Option<&PartReport>) -> String {
+    match report {
+        None => "not run".into(),
+        Some(report) => match report.status {
+            Status::Solved => format!("`{}`", report.answer.as_deref().unwrap_or_default()),
+            Status::Unsolved => "no answer".into(),
+            Status::Failed => "failed".into(),
+        },
+    }
+}
+
+/// Print the answers of a run, marking the ones that changed since the previous run.
+pub fn print_answers(previous: Option<&[PartReport]>, current: &[PartReport]) {
+    println!("\n{ANSI_BOLD}Answers{ANSI_RESET}");
+    if current.is_empty() {
+        println!("✖ no parts were run");
+    }
+
+    for (part, result, change) in diff_answers(previous, current) {
+        let name = if part == PARSE_PART {
+            "Parse".to_string()
+        } else {
+            format!("Part {part}")
+        };
+
+        match change {
+            Change::First => println!("  {name}: {result}"),
+            Change::Unchanged => println!("= {name}: {result}"),
+            Change::Changed { previous } => {
+                println!("{ANSI_BOLD}≠ {name}: {result}{ANSI_RESET}, was {previous}");
+            }
+        }
+    }
+}
+
+/// Print whether the example tests passed, and whether that changed since the previous run.
+pub fn print_tests(previous: Option<bool>, passed: bool) {
+    let result = if passed {
+        "✔ Tests passed"
+    } else {
+        "✖ Tests failed"
+    };
+
+    match previous {
+        Some(previous) if previous != passed => {
+            let before = if previous { "passing" } else { "failing" };
+            println!("{ANSI_BOLD}{result}{ANSI_RESET}, were {before}");
+        }
+        _ => println!("{result}"),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{
+        path::PathBuf,
+        time::{Duration, SystemTime},
+    };
+
+    use super::{changed_files, diff_answers, Change, Snapshot};
+    use crate::{
+        day,
+        template::report::{PartReport, Status},
+    };
+
+    fn report(part: u8, answer: Option<&str>, status: Status) -> PartReport {
+        PartReport::test(day!(1), part, answer, status)
+    }
+
+    #[test]
+    fn finds_changed_files() {
+        let t = SystemTime::UNIX_EPOCH;
+        let later = t + Duration::from_secs(1);
+        let previous: Snapshot = [("a".into(), t), ("b".into(), t), ("c".into(), t)].into();
+        let current: Snapshot = [("a".into(), t), ("b".into(), later), ("d".into(), t)].into();
+
+        let expected: Vec<PathBuf> = vec!["b".into(), "c".into(), "d".into()];
+        assert_eq!(changed_files(&previous, &current), expected);
+        assert!(changed_files(&current, &current).is_empty());
+    }
+
+    #[test]
+    fn diffs_answers() {
+        let current = [
+            report(0, None, Status::Solved),
+            report(1, Some("3"), Status::Solved),
+            report(2, Some("7"), Status::Solved),
+        ];
+
+        let first = diff_answers(None, &current);
+        assert_eq!(first.len(), 2);
+        assert!(first.iter().all(|(_, _, change)| *change == Change::First));
+
+        let previous = [
+            report(1, Some("3"), Status::Solved),
+            report(2, None, Status::Failed),
+        ];
+        assert_eq!(
+            diff_answers(Some(&previous), &current),
+            vec![
+                (1, "`3`".into(), Change::Unchanged),
+                (
+                    2,
+                    "`7`".into(),
+                    Change::Changed {
+                        previous: "failed".into()
+                    }
+                ),
+            ]
+        );
+    }
+}